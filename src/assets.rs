@@ -87,19 +87,7 @@ pub struct GameAssets {
     pub icon_branding: Handle<Image>,
     // icon end
 
-    // item start
-    #[asset(path = "items/glue.png")]
-    pub item_glue: Handle<Image>,
-
-    #[asset(path = "items/placebo.png")]
-    pub item_placebo: Handle<Image>,
-
-    #[asset(path = "items/schoolbag.png")]
-    pub item_schoolbag: Handle<Image>,
-
-    #[asset(path = "items/wheel.png")]
-    pub item_wheel: Handle<Image>,
-    // item end
+    // 道具图标由 items.ron 指定，在 item::init 中加载
 
     // sound start
     #[asset(path = "sounds/tennis.ogg")]
@@ -118,5 +106,11 @@ pub struct GameAssets {
 
     #[asset(path = "music/Long Away Home.ogg")]
     pub gaming_bgm: Handle<AudioSource>,
+
+    #[asset(path = "music/Heavy_ConceptB.ogg")]
+    pub combat_bgm: Handle<AudioSource>,
+
+    #[asset(path = "music/boss_battle_2.ogg")]
+    pub boss_bgm: Handle<AudioSource>,
     // music end
 }
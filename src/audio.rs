@@ -1,27 +1,28 @@
 use bevy::prelude::*;
-use bevy::utils::HashSet;
+use bevy::utils::HashMap;
 use bevy_kira_audio::prelude::*;
 use bevy_kira_audio::AudioSource;
 use rand::{thread_rng, Rng};
 
 mod bgm;
+mod mixer;
+mod spatial;
+
+pub use mixer::{AudioBus, AudioMixer, DuckMusicEvent};
+
+use mixer::{MusicDucking, Voice};
 
 pub struct GameAudioPlugin;
 
 impl Plugin for GameAudioPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<PlaySound>()
-            .insert_resource(Volume(1))
             .add_plugins(AudioPlugin)
-            .add_plugins((bgm::BgmPlugin,))
+            .add_plugins((bgm::BgmPlugin, mixer::MixerPlugin, spatial::SpatialPlugin))
             .add_systems(Update, play_sounds);
     }
 }
 
-// One of the two settings that can be set through the menu. It will be a resource in the app
-#[derive(Resource, Debug, Component, PartialEq, Eq, Clone, Copy)]
-pub struct Volume(pub u32);
-
 #[derive(Event)]
 pub struct PlaySound {
     pub clip: Handle<AudioSource>,
@@ -30,7 +31,11 @@ pub struct PlaySound {
     pub rand_speed_intensity: f64,
     pub repeat: bool,
     pub reverse: bool,
+    // 声音跟随的实体，用于声像定位
     pub parent: Option<Entity>,
+    // 固定位置的声音，例如死亡时实体已经被销毁
+    pub position: Option<Vec2>,
+    pub bus: AudioBus,
 }
 
 impl Default for PlaySound {
@@ -43,40 +48,76 @@ impl Default for PlaySound {
             repeat: false,
             reverse: false,
             parent: None,
+            position: None,
+            bus: AudioBus::Sfx,
+        }
+    }
+}
+
+impl PlaySound {
+    // 碰撞音效，音量和音高随撞击速度变化
+    pub fn impact(clip: Handle<AudioSource>, impact_speed: f32) -> Self {
+        let (volume, playback_rate) = spatial::impact(impact_speed);
+        Self {
+            clip,
+            volume,
+            playback_rate,
+            ..default()
         }
     }
 }
 
 fn play_sounds(
     mut commands: Commands,
-    // audio: Res<Audio>,
-    mut audio: ResMut<DynamicAudioChannels>,
-    volume: Res<Volume>,
+    audio: Res<DynamicAudioChannels>,
+    mixer: Res<AudioMixer>,
+    ducking: Res<MusicDucking>,
+    q_voice: Query<&Voice>,
+    q_transform: Query<&GlobalTransform>,
+    q_receiver: Query<&GlobalTransform, With<SpatialAudioReceiver>>,
     mut ev_play_sound: EventReader<PlaySound>,
 ) {
     let mut rng = thread_rng();
-    let mut added_sounds: HashSet<Handle<AudioSource>> = HashSet::new();
+    let receiver_x = q_receiver
+        .get_single()
+        .map_or(0.0, |transform| transform.translation().x);
+    // 每个片段当前的发声数，超过上限的声音直接丢弃
+    let mut voices: HashMap<AssetId<AudioSource>, usize> = HashMap::new();
+    for voice in q_voice.iter() {
+        *voices.entry(voice.clip).or_default() += 1;
+    }
 
     for ev in ev_play_sound.read() {
-        if added_sounds.contains(&ev.clip) {
+        let count = voices.entry(ev.clip.id()).or_default();
+        if *count >= mixer.max_voices_per_clip {
             continue;
         }
-
-        println!("sound event");
-        added_sounds.insert(ev.clip.clone());
+        *count += 1;
 
         let speed_offset = if ev.rand_speed_intensity == 0.0 {
             0.0
         } else {
             rng.gen_range(-1.0..1.0) * ev.rand_speed_intensity
         };
-        let volume_offset = if ev.parent.is_some() { 0.0 } else { 1.0 };
 
-        let mut audio_channel = audio.create_channel("gaming_bgm").play(ev.clip.clone());
+        // 跟随实体的声音取实体当前的位置作为初始声像
+        let emitter_x = match (ev.parent, ev.position) {
+            (Some(parent), _) => q_transform
+                .get(parent)
+                .ok()
+                .map(|transform| transform.translation().x),
+            (None, Some(position)) => Some(position.x),
+            (None, None) => None,
+        };
+
+        let mut audio_channel = audio.channel(ev.bus.channel()).play(ev.clip.clone());
         audio_channel
-            .with_volume(ev.volume * volume_offset * volume.0 as f64)
+            .with_volume(ev.volume * mixer.gain(ev.bus) * ducking.gain(ev.bus))
             .with_playback_rate(ev.playback_rate + speed_offset);
 
+        if let Some(x) = emitter_x {
+            audio_channel.with_panning(spatial::panning(x, receiver_x));
+        }
         if ev.repeat {
             audio_channel.looped();
         }
@@ -84,27 +125,29 @@ fn play_sounds(
             audio_channel.reverse();
         }
 
-        let audio_instance = audio_channel.handle();
+        let voice = commands
+            .spawn(Voice::new(
+                audio_channel.handle(),
+                &ev.clip,
+                ev.bus,
+                ev.volume,
+            ))
+            .id();
 
         if let Some(parent) = ev.parent {
-            let audio_emitter = commands
-                .spawn((
-                    Transform::default(),
-                    GlobalTransform::default(),
-                    SpatialAudioEmitter {
-                        instances: vec![audio_instance],
-                    },
-                ))
-                .id();
-
+            commands.entity(voice).insert(Transform::default());
             match commands.get_entity(parent) {
                 Some(mut r) => {
-                    r.add_child(audio_emitter);
+                    r.add_child(voice);
                 }
                 None => {
                     warn!("audio parent does not exist");
                 }
             };
-        };
+        } else if let Some(position) = ev.position {
+            commands
+                .entity(voice)
+                .insert(Transform::from_translation(position.extend(0.0)));
+        }
     }
 }
@@ -1,66 +1,167 @@
+use std::fs::File;
 use std::time::Duration;
 
 use bevy::prelude::*;
+use bevy::utils::HashMap;
 use bevy_kira_audio::prelude::*;
+use bevy_kira_audio::AudioSource;
+use ron::de::from_reader;
+use serde::Deserialize;
 
+use crate::world::map::room::{
+    control::ChooseState, loading::LoadingState, select::SelectedRooms, RoomType,
+};
 use crate::{GameAssets, GameState};
 
-use super::Volume;
+use super::mixer::{AudioBus, AudioMixer, MusicDucking, Voice};
+
+// 背景音乐本身的音量，最终还要乘上音乐总线
+const BGM_VOLUME: f64 = 0.5;
 
 pub struct BgmPlugin;
 
 impl Plugin for BgmPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Splash), play_menu_bgm)
-            .add_systems(OnEnter(GameState::Gaming), play_gaming_bgm)
+        app.add_event::<PlayMusicEvent>()
+            .init_resource::<CurrentMusic>()
+            .add_systems(Startup, (init_music_config, create_music_channel))
+            .add_systems(OnEnter(GameState::Splash), play_menu_bgm)
+            .add_systems(OnEnter(GameState::Gaming), play_start_room_bgm)
+            .add_systems(
+                OnEnter(LoadingState::FadeIn),
+                play_room_bgm.run_if(in_state(GameState::Gaming)),
+            )
+            .add_systems(
+                OnEnter(ChooseState::Choosing),
+                play_cleared_bgm.run_if(in_state(GameState::Gaming)),
+            )
             .add_systems(
                 Update,
-                (update_bgm_volumes,).run_if(in_state(GameState::Menu)),
+                switch_music.run_if(not(in_state(GameState::AssetLoading))),
             );
     }
 }
 
-fn play_menu_bgm(
-    mut audio: ResMut<DynamicAudioChannels>,
-    game_assets: Res<GameAssets>,
-    volume: Res<Volume>,
-) {
-    if let Some(channel) = audio.get_channel("game_bgm") {
-        channel.pause();
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MusicTrack {
+    Menu,
+    Calm,
+    Combat,
+    Boss,
+}
+
+impl MusicTrack {
+    fn clip(&self, game_assets: &GameAssets) -> Handle<AudioSource> {
+        match self {
+            MusicTrack::Menu => game_assets.menu_bgm.clone(),
+            MusicTrack::Calm => game_assets.gaming_bgm.clone(),
+            MusicTrack::Combat => game_assets.combat_bgm.clone(),
+            MusicTrack::Boss => game_assets.boss_bgm.clone(),
+        }
     }
-    audio
-        .create_channel("menu_bgm")
-        .play(game_assets.menu_bgm.clone())
-        .with_volume(volume.0 as f64 / 2.0)
-        .fade_in(AudioTween::new(
-            Duration::from_secs(2),
-            AudioEasing::OutPowi(2),
-        ))
-        .looped();
 }
 
-fn play_gaming_bgm(
-    mut audio: ResMut<DynamicAudioChannels>,
-    game_assets: Res<GameAssets>,
-    volume: Res<Volume>,
-) {
-    if let Some(channel) = audio.get_channel("menu_bgm") {
-        channel.pause();
+// 每种房间播放什么音乐，从 music.ron 读取
+#[derive(Resource, Deserialize, Debug)]
+pub struct MusicConfig {
+    // 房间里还有敌人时播放的曲目
+    pub room_tracks: HashMap<RoomType, MusicTrack>,
+    // 房间清空之后播放的曲目
+    pub cleared_track: MusicTrack,
+    pub crossfade_seconds: f32,
+}
+
+impl MusicConfig {
+    fn room_track(&self, room_type: RoomType) -> MusicTrack {
+        self.room_tracks
+            .get(&room_type)
+            .copied()
+            .unwrap_or(self.cleared_track)
     }
-    audio
-        .create_channel("gaming_bgm")
-        .play(game_assets.gaming_bgm.clone())
-        .with_volume(volume.0 as f64 / 2.0)
-        .looped();
 }
 
-fn update_bgm_volumes(audio: Res<DynamicAudioChannels>, volume: Res<Volume>) {
-    if volume.is_changed() {
-        if let Some(channel) = audio.get_channel("menu_bgm") {
-            channel.set_volume(volume.0 as f64);
-        }
-        if let Some(channel) = audio.get_channel("gaming_bgm") {
-            channel.set_volume(volume.0 as f64);
-        }
+// 切换背景音乐，和当前曲目相同时不做任何事
+#[derive(Event)]
+pub struct PlayMusicEvent(pub MusicTrack);
+
+#[derive(Resource, Default)]
+struct CurrentMusic {
+    track: Option<MusicTrack>,
+}
+
+fn init_music_config(mut commands: Commands) {
+    let file = File::open("assets/music/music.ron").expect("Failed to open music.ron");
+    let config: MusicConfig = from_reader(file).expect("Unable to load music.ron");
+    commands.insert_resource(config);
+}
+
+// 音乐通道只创建一次，重新创建会丢掉通道里还没执行的命令
+fn create_music_channel(mut audio: ResMut<DynamicAudioChannels>) {
+    audio.create_channel(AudioBus::Music.channel());
+}
+
+fn play_menu_bgm(mut music_events: EventWriter<PlayMusicEvent>) {
+    music_events.send(PlayMusicEvent(MusicTrack::Menu));
+}
+
+fn play_start_room_bgm(mut music_events: EventWriter<PlayMusicEvent>, config: Res<MusicConfig>) {
+    music_events.send(PlayMusicEvent(config.room_track(RoomType::Start)));
+}
+
+// 新房间加载完成，淡入的同时切换到该房间的音乐
+fn play_room_bgm(
+    mut music_events: EventWriter<PlayMusicEvent>,
+    config: Res<MusicConfig>,
+    selected_rooms: Res<SelectedRooms>,
+) {
+    let Some(room) = selected_rooms.rooms.get(selected_rooms.index) else {
+        return;
+    };
+    let track = if room.encounter.is_some() {
+        config.room_track(room.room_type)
+    } else {
+        config.cleared_track
+    };
+    music_events.send(PlayMusicEvent(track));
+}
+
+fn play_cleared_bgm(mut music_events: EventWriter<PlayMusicEvent>, config: Res<MusicConfig>) {
+    music_events.send(PlayMusicEvent(config.cleared_track));
+}
+
+// 旧曲目淡出，新曲目淡入
+fn switch_music(
+    mut commands: Commands,
+    mut music_events: EventReader<PlayMusicEvent>,
+    mut current: ResMut<CurrentMusic>,
+    audio: Res<DynamicAudioChannels>,
+    game_assets: Res<GameAssets>,
+    config: Res<MusicConfig>,
+    mixer: Res<AudioMixer>,
+    ducking: Res<MusicDucking>,
+) {
+    let Some(PlayMusicEvent(track)) = music_events.read().last() else {
+        return;
+    };
+    if current.track == Some(*track) {
+        return;
     }
+
+    let crossfade = AudioTween::new(
+        Duration::from_secs_f32(config.crossfade_seconds),
+        AudioEasing::OutPowi(2),
+    );
+    // 通过通道停止旧曲目，即使它的实例还没有创建出来也会被停掉
+    let channel = audio.channel(AudioBus::Music.channel());
+    channel.stop().fade_out(crossfade.clone());
+
+    let clip = track.clip(&game_assets);
+    let instance = channel
+        .play(clip.clone())
+        .with_volume(BGM_VOLUME * mixer.gain(AudioBus::Music) * ducking.gain(AudioBus::Music))
+        .fade_in(crossfade)
+        .looped()
+        .handle();
+    commands.spawn(Voice::new(instance, &clip, AudioBus::Music, BGM_VOLUME));
+    current.track = Some(*track);
 }
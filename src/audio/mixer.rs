@@ -0,0 +1,261 @@
+use bevy::prelude::*;
+use bevy_kira_audio::prelude::*;
+use bevy_kira_audio::AudioSource;
+use serde::{Deserialize, Serialize};
+
+pub struct MixerPlugin;
+
+impl Plugin for MixerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DuckMusicEvent>()
+            .init_resource::<MusicDucking>()
+            .add_systems(Startup, create_channels)
+            .add_systems(
+                PostUpdate,
+                (update_music_ducking, apply_mixer, cleanup_voices).chain(),
+            );
+    }
+}
+
+// 音频总线，最终音量 = 声音本身的音量 * 主线 * 所在总线
+#[derive(Component, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AudioBus {
+    Master,
+    Music,
+    Sfx,
+    Ui,
+}
+
+impl AudioBus {
+    // 音效和UI各用一个kira通道，音乐由bgm自己管理通道
+    pub fn channel(&self) -> &'static str {
+        match self {
+            AudioBus::Master => "master",
+            AudioBus::Music => "music",
+            AudioBus::Sfx => "sfx",
+            AudioBus::Ui => "ui",
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct BusSettings {
+    pub volume: f32,
+    pub muted: bool,
+}
+
+impl Default for BusSettings {
+    fn default() -> Self {
+        Self {
+            volume: 1.0,
+            muted: false,
+        }
+    }
+}
+
+impl BusSettings {
+    pub fn gain(&self) -> f64 {
+        if self.muted {
+            0.0
+        } else {
+            self.volume.clamp(0.0, 1.0) as f64
+        }
+    }
+}
+
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct AudioMixer {
+    pub master: BusSettings,
+    pub music: BusSettings,
+    pub sfx: BusSettings,
+    pub ui: BusSettings,
+    // 同一个音频片段最多同时播放几个，超出的直接丢弃
+    pub max_voices_per_clip: usize,
+}
+
+impl Default for AudioMixer {
+    fn default() -> Self {
+        Self {
+            master: BusSettings::default(),
+            music: BusSettings::default(),
+            sfx: BusSettings::default(),
+            ui: BusSettings::default(),
+            max_voices_per_clip: 4,
+        }
+    }
+}
+
+impl AudioMixer {
+    pub fn bus(&self, bus: AudioBus) -> &BusSettings {
+        match bus {
+            AudioBus::Master => &self.master,
+            AudioBus::Music => &self.music,
+            AudioBus::Sfx => &self.sfx,
+            AudioBus::Ui => &self.ui,
+        }
+    }
+
+    pub fn bus_mut(&mut self, bus: AudioBus) -> &mut BusSettings {
+        match bus {
+            AudioBus::Master => &mut self.master,
+            AudioBus::Music => &mut self.music,
+            AudioBus::Sfx => &mut self.sfx,
+            AudioBus::Ui => &mut self.ui,
+        }
+    }
+
+    pub fn gain(&self, bus: AudioBus) -> f64 {
+        match bus {
+            AudioBus::Master => self.master.gain(),
+            _ => self.master.gain() * self.bus(bus).gain(),
+        }
+    }
+}
+
+// 压低音乐，例如显示道具拾取提示的时候
+#[derive(Event)]
+pub struct DuckMusicEvent {
+    pub volume: f32,
+    pub seconds: f32,
+}
+
+#[derive(Resource)]
+pub struct MusicDucking {
+    pub current: f32,
+    target: f32,
+    timer: Timer,
+}
+
+impl Default for MusicDucking {
+    fn default() -> Self {
+        Self {
+            current: 1.0,
+            target: 1.0,
+            timer: Timer::from_seconds(0.0, TimerMode::Once),
+        }
+    }
+}
+
+// 压低和恢复的速度（每秒）
+const DUCKING_SPEED: f32 = 3.0;
+
+impl MusicDucking {
+    pub fn gain(&self, bus: AudioBus) -> f64 {
+        match bus {
+            AudioBus::Music => self.current as f64,
+            _ => 1.0,
+        }
+    }
+}
+
+// 正在播放的声音，用来在总线音量变化时重新计算音量
+#[derive(Component)]
+pub struct Voice {
+    pub instance: Handle<AudioInstance>,
+    pub clip: AssetId<AudioSource>,
+    pub bus: AudioBus,
+    pub volume: f64,
+    // 播放命令在PostUpdate才被处理，实例出现之前不能当作已经停止
+    started: bool,
+    queued_frames: u32,
+}
+
+impl Voice {
+    pub fn new(
+        instance: Handle<AudioInstance>,
+        clip: &Handle<AudioSource>,
+        bus: AudioBus,
+        volume: f64,
+    ) -> Self {
+        Self {
+            instance,
+            clip: clip.id(),
+            bus,
+            volume,
+            started: false,
+            queued_frames: 0,
+        }
+    }
+}
+
+// 超过这个帧数还没出现实例的声音视为播放失败
+const MAX_QUEUED_FRAMES: u32 = 60;
+
+// create_channel 会替换掉同名的通道，连同通道里还没执行的播放命令，所以只在启动时创建一次
+// 主音量只是乘在各总线上的倍率，音乐的通道在 bgm 里创建
+fn create_channels(mut audio: ResMut<DynamicAudioChannels>) {
+    for bus in [AudioBus::Sfx, AudioBus::Ui] {
+        audio.create_channel(bus.channel());
+    }
+}
+
+fn update_music_ducking(
+    mut ducking: ResMut<MusicDucking>,
+    mut events: EventReader<DuckMusicEvent>,
+    time: Res<Time>,
+) {
+    // 计时器每帧都会变化，只有current变化时才触发change detection
+    let state = ducking.bypass_change_detection();
+    for event in events.read() {
+        state.target = state.target.min(event.volume);
+        state.timer = Timer::from_seconds(event.seconds, TimerMode::Once);
+    }
+    state.timer.tick(time.delta());
+    if state.timer.finished() {
+        state.target = 1.0;
+    }
+
+    let step = DUCKING_SPEED * time.delta_secs();
+    let next = if state.current < state.target {
+        (state.current + step).min(state.target)
+    } else {
+        (state.current - step).max(state.target)
+    };
+    if next != ducking.current {
+        ducking.current = next;
+    }
+}
+
+fn apply_mixer(
+    mixer: Res<AudioMixer>,
+    ducking: Res<MusicDucking>,
+    q_voice: Query<&Voice>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+) {
+    if !mixer.is_changed() && !ducking.is_changed() {
+        return;
+    }
+    for voice in q_voice.iter() {
+        if let Some(instance) = audio_instances.get_mut(&voice.instance) {
+            instance.set_volume(
+                voice.volume * mixer.gain(voice.bus) * ducking.gain(voice.bus),
+                AudioTween::default(),
+            );
+        }
+    }
+}
+
+fn cleanup_voices(
+    mut commands: Commands,
+    audio_instances: Res<Assets<AudioInstance>>,
+    mut q_voice: Query<(Entity, &mut Voice)>,
+) {
+    for (entity, mut voice) in q_voice.iter_mut() {
+        let stopped = match audio_instances.get(&voice.instance) {
+            Some(instance) => {
+                voice.started = true;
+                instance.state() == PlaybackState::Stopped
+            }
+            None if voice.started => true,
+            None => {
+                voice.queued_frames += 1;
+                voice.queued_frames > MAX_QUEUED_FRAMES
+            }
+        };
+        if stopped {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
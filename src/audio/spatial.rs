@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+use bevy_kira_audio::prelude::*;
+
+use crate::WINDOW_WIDTH;
+
+use super::mixer::Voice;
+
+pub struct SpatialPlugin;
+
+impl Plugin for SpatialPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            pan_voices.after(TransformSystem::TransformPropagate),
+        );
+    }
+}
+
+// 声源在竞技场最左/最右时的声像，1.0 表示完全偏到一侧
+const MAX_PAN: f32 = 0.8;
+
+// 撞击速度达到这个值时音量和音高达到最大
+const IMPACT_SPEED_FULL: f32 = 1000.0;
+
+// 根据声源相对接收者（MainCamera）的横向位置计算声像，0.5 为居中
+pub fn panning(emitter_x: f32, receiver_x: f32) -> f64 {
+    let offset = ((emitter_x - receiver_x) / (WINDOW_WIDTH / 2.0)).clamp(-1.0, 1.0);
+    (0.5 + offset * MAX_PAN / 2.0) as f64
+}
+
+// 撞击越重声音越大、音调越高，返回 (音量, 播放速度)
+pub fn impact(impact_speed: f32) -> (f64, f64) {
+    let t = (impact_speed / IMPACT_SPEED_FULL).clamp(0.0, 1.0) as f64;
+    (0.2 + 0.8 * t, 0.85 + 0.3 * t)
+}
+
+// 有位置的声音会跟着声源移动，每帧更新声像
+fn pan_voices(
+    receiver: Query<&GlobalTransform, With<SpatialAudioReceiver>>,
+    q_voice: Query<(&Voice, &GlobalTransform)>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+) {
+    let Ok(receiver_transform) = receiver.get_single() else {
+        return;
+    };
+    let receiver_x = receiver_transform.translation().x;
+    for (voice, transform) in q_voice.iter() {
+        if let Some(instance) = audio_instances.get_mut(&voice.instance) {
+            instance.set_panning(
+                panning(transform.translation().x, receiver_x),
+                AudioTween::default(),
+            );
+        }
+    }
+}
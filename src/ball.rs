@@ -1,41 +1,73 @@
 mod collision;
+mod physics;
 
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
+use serde::Deserialize;
 
-// 刚度系数
-pub const STIFFNESS: f32 = 1000.0;
 // 流体密度
 pub const FLUID_DENSITY: f32 = 1.83;
 // 马格努斯系数
 pub const MAGNUS_COEFFICIENT: f32 = 0.3;
 // 旋转阻尼系数
 pub const DAMPING_COEFFICIENT: f32 = 0.8;
+// 砖块横向移动时传给球的旋转（每单位速度、每单位摩擦）
+pub const PADDLE_SPIN_FACTOR: f32 = 0.01;
+// 球的最大角速度
+pub const MAX_SPIN: f32 = 30.0;
 
 pub struct BallPlugin;
 
 impl Plugin for BallPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((collision::CollisionPlugin,));
+        app.init_resource::<PaddleDeflection>()
+            .add_plugins((collision::CollisionPlugin,));
     }
 }
 
-#[derive(Component, Default, Clone, Copy, Debug)]
+#[derive(Component, Deserialize, Default, Clone, Copy, Debug, PartialEq)]
 #[require(OriginalVel)]
 pub enum Ball {
     #[default]
     Tennis,
 }
 
+impl Ball {
+    // 球名称的本地化文本键
+    pub fn key(&self) -> &'static str {
+        match self {
+            Ball::Tennis => "ball.tennis",
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct DamageCoefficient(pub f32);
 
-#[derive(Component)]
-pub struct TargetForce(pub Vec2);
+// 球落在砖块上表面时，击球位置决定反弹角度
+#[derive(Resource, Debug, Clone)]
+pub struct PaddleDeflection {
+    // 打在砖块边缘时偏离竖直方向的最大角度（弧度）
+    pub max_angle: f32,
+    // 正中间的完美击球区域，占半个砖块宽度的比例，None 表示关闭
+    pub perfect_window: Option<f32>,
+    // 完美击球时速度的倍率
+    pub perfect_speed_boost: f32,
+    // 完美击球后球的伤害系数至少为这个值
+    pub perfect_damage_coefficient: f32,
+    // 速度加成的上限
+    pub max_speed: f32,
+}
 
-impl Default for TargetForce {
+impl Default for PaddleDeflection {
     fn default() -> Self {
-        Self(Vec2::ZERO)
+        Self {
+            max_angle: 60f32.to_radians(),
+            perfect_window: Some(0.1),
+            perfect_speed_boost: 1.2,
+            perfect_damage_coefficient: 1.5,
+            max_speed: 1000.0,
+        }
     }
 }
 
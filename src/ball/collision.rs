@@ -1,308 +1,298 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
+use bevy_rapier2d::rapier::prelude::CollisionEventFlags;
 
-use std::f32::consts::PI;
-use std::ops::Neg;
-
-use crate::ball::Ball;
+use crate::audio::PlaySound;
+use crate::ball::{Ball, DamageCoefficient, PaddleDeflection};
 use crate::brick::brick_ball::BRICK_BALL_SIZE;
-use crate::brick::BallInHand;
-use crate::{GameState, WINDOW_HEIGHT, WINDOW_WIDTH};
+use crate::brick::{BallInHand, Brick, Dimensions};
+use crate::effects::{BurstKind, ParticleBurstEvent};
+use crate::enemy::Enemy;
+use crate::{GameAssets, GameState, WINDOW_HEIGHT, WINDOW_WIDTH};
 
-use super::{
-    OriginalVel, TargetForce, DAMPING_COEFFICIENT, FLUID_DENSITY, MAGNUS_COEFFICIENT, STIFFNESS,
+use super::physics::{
+    clamp_spin, contact_response, is_perfect_hit, magnus_acceleration, paddle_deflection,
+    paddle_spin, preserve_speed, spin_decay, BallState, ContactParams,
 };
+use super::OriginalVel;
+
+const BALL_RADIUS: f32 = BRICK_BALL_SIZE / 2.0;
+// 法线和竖直方向的夹角小于这个值（的余弦）才算打在砖块上表面
+const TOP_FACE_NORMAL: f32 = 0.7;
 
 pub struct CollisionPlugin;
 
 impl Plugin for CollisionPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<ContactEvent>()
-            .add_event::<ContactEventPart2>()
-            .add_event::<ForceEvent>()
+        // 球的模拟放在固定时间步里，紧跟在物理引擎写回结果之后，弹跳不再受帧率影响
+        app.add_event::<PerfectHitEvent>()
             .add_systems(
-                Update,
-                (
-                    ball_collision_handle,
-                    contact_event_handle,
-                    contact_event_part2_handle,
-                    apply_elastic_force,
-                )
+                FixedUpdate,
+                (ball_contacts, ball_flight)
                     .chain()
+                    .after(PhysicsSet::Writeback)
                     .run_if(in_state(GameState::Gaming)),
             )
-            .add_systems(Update, (despawn_ball).run_if(in_state(GameState::Gaming)));
+            .add_systems(
+                Update,
+                (despawn_ball, ball_impact_feedback, perfect_hit_feedback)
+                    .run_if(in_state(GameState::Gaming)),
+            );
     }
 }
 
+// 在砖块正中间击球
 #[derive(Event)]
-pub struct ContactEvent {
-    other_children_entity: Entity,
-    ball_children_entity: Entity,
-    direction: Vec2,
-    depth: f32,
+pub struct PerfectHitEvent {
+    pub ball: Entity,
+    pub pos: Vec2,
 }
 
-#[derive(Event)]
-pub struct ContactEventPart2 {
-    other_velocity: Velocity,
-    ball_parent_entity: Entity,
-    direction: Vec2,
-    depth: f32,
-    friction: f32,
-    resititution: f32,
-    ball_mass: ColliderMassProperties,
-    other_mass: ColliderMassProperties,
-}
-
-fn ball_collision_handle(
+// 读取物理引擎的接触信息，对每个接触点计算一次碰撞响应
+fn ball_contacts(
     rapier_context: ReadRapierContext,
-    mut q_ball_children: Query<
-        (&Children, &OriginalVel, &mut Velocity),
+    mut q_ball: Query<
+        (
+            Entity,
+            &Children,
+            &GlobalTransform,
+            &mut OriginalVel,
+            &mut Velocity,
+            Option<&mut DamageCoefficient>,
+        ),
         (With<Ball>, Without<BallInHand>),
     >,
-    mut contact_event_writer: EventWriter<ContactEvent>,
-    time: Res<Time>,
+    q_collider: Query<(
+        Option<&Parent>,
+        &GlobalTransform,
+        Option<&Friction>,
+        Option<&Restitution>,
+    )>,
+    // 球和球之间的碰撞把对方当作静止的
+    q_velocity: Query<&Velocity, Without<Ball>>,
+    // 砖块刚体上的摩擦会被道具修改（例如胶水）
+    q_brick: Query<(&GlobalTransform, &Dimensions, &Friction), With<Brick>>,
+    deflection: Res<PaddleDeflection>,
+    mut perfect_hit_events: EventWriter<PerfectHitEvent>,
 ) {
-    for (children, ball_original_vel, mut ball_velocity) in q_ball_children.iter_mut() {
+    let Ok((simulation, colliders, _, _, rigidbody_set)) =
+        rapier_context.rapier_context.get_single()
+    else {
+        return;
+    };
+    for (ball, children, ball_transform, mut original_vel, mut velocity, damage_coefficient) in
+        q_ball.iter_mut()
+    {
         // collider是附加在ball的子实体上
-        let ball_children_entity = children[0];
-        let mut direction = Vec2::ZERO;
-        let mut depth = 0.0;
-        let mut in_contact = false;
-        let mut other_children_entity: Option<Entity> = None;
+        let Some(&ball_collider) = children.first() else {
+            continue;
+        };
+        let Ok((_, _, ball_friction, ball_restitution)) = q_collider.get(ball_collider) else {
+            continue;
+        };
 
-        for contact_pair in rapier_context
-            .single()
-            .contact_pairs_with(ball_children_entity)
-        {
+        let mut perfect_hit = false;
+        let mut state = BallState {
+            linvel: velocity.linvel,
+            angvel: velocity.angvel,
+        };
+        for contact_pair in simulation.contact_pairs_with(colliders, rigidbody_set, ball_collider) {
             // 根据Collision groups 来判断有没有active_contact
-            if contact_pair.has_any_active_contact() {
-                in_contact = true;
-                for manifold in contact_pair.manifolds() {
-                    depth = manifold.point(0).unwrap().dist().abs();
-                    if contact_pair.collider1() == ball_children_entity {
-                        direction = manifold.local_n2();
-                        other_children_entity = Some(contact_pair.collider2());
-                    } else {
-                        direction = manifold.local_n1();
-                        other_children_entity = Some(contact_pair.collider1());
-                    }
+            if !contact_pair.has_any_active_contact() {
+                continue;
+            }
+            let ball_is_first = contact_pair.collider1() == ball_collider;
+            let other_collider = if ball_is_first {
+                contact_pair.collider2()
+            } else {
+                contact_pair.collider1()
+            };
+            let Ok((other_parent, other_transform, other_friction, other_restitution)) =
+                q_collider.get(other_collider)
+            else {
+                continue;
+            };
+            let other_body = other_parent.map(|parent| parent.get());
+            let other_velocity = other_body
+                .and_then(|body| q_velocity.get(body).ok())
+                .map_or(Vec2::ZERO, |v| v.linvel);
+            let brick = other_body.and_then(|body| q_brick.get(body).ok());
+            // 道具修改的是砖块本体上的摩擦，子实体碰撞体上的还是角色的初始值
+            let other_friction = brick.map_or(other_friction, |(.., friction)| Some(friction));
 
-                    for s in &manifold.raw.data.solver_contacts {
-                        println!("point: {}", s.point);
-                        println!("dist: {}", s.dist);
-                        println!("fric: {}", s.friction);
-                        println!("rest: {}", s.restitution);
-                        println!("tangent_vel: {}", s.tangent_velocity);
-                        println!("warmstart_impluse: {}", s.warmstart_impulse);
-                        println!("warmstart_tangnt_impluse: {}", s.warmstart_tangent_impulse);
+            for manifold in contact_pair.manifolds() {
+                if manifold.num_points() == 0 {
+                    continue;
+                }
+                // 取被撞物体一侧的法线，它从被撞物体指向球
+                let local_normal = if ball_is_first {
+                    manifold.local_n2()
+                } else {
+                    manifold.local_n1()
+                };
+                let normal = (other_transform.rotation() * local_normal.extend(0.0))
+                    .truncate()
+                    .normalize_or_zero();
+                let before = state;
+                state = contact_response(
+                    state,
+                    ContactParams {
+                        normal,
+                        other_velocity,
+                        radius: BALL_RADIUS,
+                        restitution: average(
+                            ball_restitution.map(|r| r.coefficient),
+                            other_restitution.map(|r| r.coefficient),
+                        ),
+                        friction: average(
+                            ball_friction.map(|f| f.coefficient),
+                            other_friction.map(|f| f.coefficient),
+                        ),
+                    },
+                );
+                if let Some((brick_transform, dimensions, friction)) = brick {
+                    if state != before {
+                        // 落在砖块上表面时，由击球位置决定反弹方向，速率不变
+                        if normal.y > TOP_FACE_NORMAL {
+                            let hit_offset =
+                                ball_transform.translation().x - brick_transform.translation().x;
+                            let half_width = dimensions.width / 2.0;
+                            state.linvel =
+                                paddle_deflection(hit_offset, half_width, deflection.max_angle)
+                                    * state.linvel.length();
+                            perfect_hit |= deflection.perfect_window.is_some_and(|window| {
+                                is_perfect_hit(hit_offset, half_width, window)
+                            });
+                        }
+                        // 击球瞬间横向移动砖块可以主动搓出旋转
+                        state.angvel += paddle_spin(other_velocity, normal, friction.coefficient);
                     }
                 }
+                state.angvel = clamp_spin(state.angvel);
             }
-            // println!("normal: {}", direction);
-            // q_parent.get(contact_pair.collider2()).ok().unwrap().get()
         }
 
-        if in_contact {
-            contact_event_writer.send(ContactEvent {
-                other_children_entity: other_children_entity.unwrap(),
-                ball_children_entity,
-                direction,
-                depth,
+        // 完美击球提高出手速率和伤害
+        if perfect_hit {
+            let speed = original_vel.0.linvel.length();
+            if speed > 0.0 {
+                let boosted = (speed * deflection.perfect_speed_boost).min(deflection.max_speed);
+                original_vel.0.linvel = preserve_speed(original_vel.0.linvel, boosted.max(speed));
+            }
+            if let Some(mut damage_coefficient) = damage_coefficient {
+                damage_coefficient.0 = damage_coefficient
+                    .0
+                    .max(deflection.perfect_damage_coefficient);
+            }
+            perfect_hit_events.send(PerfectHitEvent {
+                ball,
+                pos: ball_transform.translation().truncate(),
             });
-        } else {
-            // 动能守恒
-            ball_velocity.linvel =
-                ball_velocity.linvel.normalize_or_zero() * ball_original_vel.0.linvel.length();
-            apppy_magnus_force(&mut ball_velocity, time.delta_secs());
+        }
+
+        if state.linvel != velocity.linvel || state.angvel != velocity.angvel {
+            velocity.linvel = keep_launch_speed(state.linvel, &original_vel);
+            velocity.angvel = state.angvel;
         }
     }
 }
 
-fn contact_event_handle(
-    mut contact_event_reader: EventReader<ContactEvent>,
-    mut contact_event_writer: EventWriter<ContactEventPart2>,
-    q_parent: Query<&Parent, With<Collider>>,
-    q_restitution: Query<&Restitution>,
-    q_friction: Query<&Friction>,
-    q_velocity: Query<&Velocity>,
-    q_mass: Query<&ColliderMassProperties>,
+// 飞行中：旋转衰减、马格努斯效应，并保持出手时的速率
+// 球的刚体没有锁定旋转，物理引擎会按 angvel 转动球的 Transform，所以精灵会跟着旋转
+fn ball_flight(
+    mut q_ball: Query<(&OriginalVel, &mut Velocity), (With<Ball>, Without<BallInHand>)>,
+    time: Res<Time>,
 ) {
-    for &ContactEvent {
-        other_children_entity,
-        ball_children_entity,
-        direction,
-        depth,
-    } in contact_event_reader.read()
-    {
-        let ball_parent_entity = q_parent.get(ball_children_entity).ok().unwrap().get();
-        let other_parent_entity = q_parent.get(other_children_entity).ok().unwrap().get();
-
-        let other_velocity = q_velocity.get(other_parent_entity).unwrap();
-
-        let ball_mass = q_mass.get(ball_children_entity).unwrap();
-        let other_mass = q_mass.get(other_children_entity).unwrap();
-
-        let ball_fric_coe = q_friction
-            .get(ball_children_entity)
-            .ok()
-            .unwrap()
-            .coefficient;
-        let other_fric_coe = q_friction
-            .get(other_children_entity)
-            .ok()
-            .unwrap()
-            .coefficient;
-        let ball_rest_coe = q_restitution
-            .get(ball_children_entity)
-            .ok()
-            .unwrap()
-            .coefficient;
-        let other_rest_coe = q_restitution
-            .get(other_children_entity)
-            .ok()
-            .unwrap()
-            .coefficient;
-
-        let avg_fric_coe = (ball_fric_coe + other_fric_coe) / 2.0;
-        let avg_rest_coe = (ball_rest_coe + other_rest_coe) / 2.0;
-        contact_event_writer.send(ContactEventPart2 {
-            other_velocity: *other_velocity,
-            ball_parent_entity,
-            direction,
-            depth,
-            friction: avg_fric_coe,
-            resititution: avg_rest_coe,
-            ball_mass: *ball_mass,
-            other_mass: *other_mass,
-        });
+    let delta_secs = time.delta_secs();
+    for (original_vel, mut velocity) in q_ball.iter_mut() {
+        velocity.angvel = spin_decay(velocity.angvel, delta_secs);
+        let magnus = magnus_acceleration(velocity.linvel, velocity.angvel, BALL_RADIUS);
+        velocity.linvel = keep_launch_speed(velocity.linvel + magnus * delta_secs, original_vel);
     }
 }
 
-#[derive(Event)]
-pub struct ForceEvent {
-    ball_entity: Entity,
-    other_velocity: Velocity,
-    direction: Vec2,
-    friction: f32,
-    restitution: f32,
-    ball_mass: ColliderMassProperties,
-    other_mass: ColliderMassProperties,
+// 摩擦和恢复系数取两者的平均值，没有设置的按 1.0 处理
+fn average(a: Option<f32>, b: Option<f32>) -> f32 {
+    (a.unwrap_or(1.0) + b.unwrap_or(1.0)) / 2.0
 }
 
-fn contact_event_part2_handle(
-    mut contact_event_reader: EventReader<ContactEventPart2>,
-    mut force_events: EventWriter<ForceEvent>,
-    mut q_ball_target_force: Query<&mut TargetForce, With<Ball>>,
-) {
-    for &ContactEventPart2 {
-        other_velocity,
-        ball_parent_entity,
-        direction,
-        depth,
-        friction,
-        resititution,
-        ball_mass,
-        other_mass,
-    } in contact_event_reader.read()
-    {
-        let mut target_force = if let Ok(tf) = q_ball_target_force.get_mut(ball_parent_entity) {
-            tf
-        } else {
-            continue;
-        };
-        target_force.0 += depth * STIFFNESS * direction;
-        force_events.send(ForceEvent {
-            ball_entity: ball_parent_entity,
-            other_velocity,
-            direction,
-            friction,
-            restitution: resititution,
-            ball_mass,
-            other_mass,
-        });
+// 还没被射出的球没有出手速度，保持原样
+fn keep_launch_speed(linvel: Vec2, original_vel: &OriginalVel) -> Vec2 {
+    let speed = original_vel.0.linvel.length();
+    if speed > 0.0 {
+        preserve_speed(linvel, speed)
+    } else {
+        linvel
     }
 }
 
-fn apply_elastic_force(
-    mut force_events: EventReader<ForceEvent>,
-    mut q_velocity: Query<&mut Velocity, With<Ball>>,
-    mut q_ball_target_force: Query<&mut TargetForce, With<Ball>>,
-    time: Res<Time>,
+// 球撞到东西时播放弹跳音效并溅出粒子，撞到敌人时声音从敌人身上发出
+fn ball_impact_feedback(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut sound_events: EventWriter<PlaySound>,
+    mut burst_events: EventWriter<ParticleBurstEvent>,
+    q_parent: Query<&Parent>,
+    q_ball: Query<Entity, (With<Ball>, Without<BallInHand>)>,
+    q_enemy: Query<Entity, With<Enemy>>,
+    q_velocity: Query<&Velocity>,
+    q_transform: Query<&GlobalTransform>,
+    game_assets: Res<GameAssets>,
 ) {
-    for &ForceEvent {
-        ball_entity,
-        other_velocity,
-        direction,
-        friction,
-        restitution,
-        ball_mass,
-        other_mass,
-    } in force_events.read()
-    {
-        let mut ball_velocity = if let Ok(res) = q_velocity.get_mut(ball_entity) {
-            res
-        } else {
-            panic!()
+    for event in collision_events.read() {
+        let CollisionEvent::Started(entity_a, entity_b, flags) = event else {
+            continue;
         };
-        let mut target_force = if let Ok(res) = q_ball_target_force.get_mut(ball_entity) {
-            res
+        if flags.contains(CollisionEventFlags::SENSOR) {
+            continue;
+        }
+        let parent_of = |entity: &Entity| q_parent.get(*entity).map_or(*entity, |p| p.get());
+        let (a, b) = (parent_of(entity_a), parent_of(entity_b));
+        let (ball, other) = if q_ball.contains(a) {
+            (a, b)
+        } else if q_ball.contains(b) {
+            (b, a)
         } else {
-            panic!()
+            continue;
         };
-        // println!("target force: {}", target_force.0);
 
-        let ball_mass_properties = match ball_mass {
-            ColliderMassProperties::MassProperties(mp) => mp,
-            _ => unreachable!(),
-        };
-        let other_mass_properties = match other_mass {
-            ColliderMassProperties::MassProperties(mp) => mp,
-            _ => unreachable!(),
-        };
-        // 相对速度 （假设球体无旋转，简化模型）
-        let v_rel = ball_velocity.linvel - other_velocity.linvel;
-        // println!(
-        //     "v_rel: {}, ball_v: {}, other_v:{}",
-        //     v_rel, ball_velocity.linvel, other_velocity.linvel
-        // );
-        // 切向相对速度
-        let v_tan = v_rel - (v_rel * direction) * direction;
-        // println!("v_tan: {}", v_tan);
-        // 法向冲量
-        let j_n = restitution * target_force.0 * time.delta_secs();
-        // 切向冲量
-        let j_t = v_tan * ball_mass_properties.mass * friction * time.delta_secs();
-        // 跟新线速度
-        ball_velocity.linvel += (j_t.neg() * 10.0 + j_n) / ball_mass_properties.mass;
-        // 跟新角速度
-        let s: f32 = if v_rel.x < 0.0 {
-            1.0
-        } else if v_rel.x > 0.0 {
-            -1.0
-        } else {
-            0.0
-        };
-        ball_velocity.angvel += j_t.length() * s / (ball_mass_properties.mass);
-        ball_velocity.angvel -= DAMPING_COEFFICIENT * ball_velocity.angvel * time.delta_secs();
-        *target_force = TargetForce::default();
+        let ball_velocity = q_velocity.get(ball).map_or(Vec2::ZERO, |v| v.linvel);
+        let other_velocity = q_velocity.get(other).map_or(Vec2::ZERO, |v| v.linvel);
+        let emitter = if q_enemy.contains(other) { other } else { ball };
+        sound_events.send(PlaySound {
+            parent: Some(emitter),
+            ..PlaySound::impact(
+                game_assets.tennis_bounce_sound.clone(),
+                (ball_velocity - other_velocity).length(),
+            )
+        });
+        if let Ok(transform) = q_transform.get(ball) {
+            burst_events.send(ParticleBurstEvent {
+                pos: transform.translation().truncate(),
+                kind: BurstKind::Impact,
+            });
+        }
     }
 }
 
-fn apppy_magnus_force(ball_velocity: &mut Velocity, delta_secs: f32) {
-    let magnus_force = FLUID_DENSITY
-        * PI
-        * ball_velocity.angvel.neg()
-        * ball_velocity.linvel.length()
-        * (BRICK_BALL_SIZE / 2.0)
-        * MAGNUS_COEFFICIENT
-        * Vec2::new(1.0, 0.0)
-        * 0.001;
-
-    // Updateprintln!("magnus f: {}", magnus_force);
-    ball_velocity.angvel -= DAMPING_COEFFICIENT * ball_velocity.angvel * delta_secs;
-    ball_velocity.linvel += magnus_force * delta_secs;
+// 完美击球：更高的弹跳音和金色的粒子
+fn perfect_hit_feedback(
+    mut perfect_hit_events: EventReader<PerfectHitEvent>,
+    mut sound_events: EventWriter<PlaySound>,
+    mut burst_events: EventWriter<ParticleBurstEvent>,
+    game_assets: Res<GameAssets>,
+) {
+    for event in perfect_hit_events.read() {
+        sound_events.send(PlaySound {
+            clip: game_assets.tennis_bounce_sound.clone(),
+            playback_rate: 1.6,
+            parent: Some(event.ball),
+            ..default()
+        });
+        burst_events.send(ParticleBurstEvent {
+            pos: event.pos,
+            kind: BurstKind::Perfect,
+        });
+    }
 }
 
 fn despawn_ball(mut commands: Commands, q_ball: Query<(&Transform, Entity), With<Ball>>) {
@@ -0,0 +1,313 @@
+// 球的接触模型，全部是纯函数，方便在固定时间步里调用和单独测试
+//
+// 约定：
+// - normal 是从被撞物体指向球的单位法线
+// - tangent 是 normal 逆时针旋转 90 度，角速度为正时球逆时针旋转
+// - 冲量都按单位质量计算，被撞物体（墙、砖块、敌人）视为质量无穷大
+use bevy::prelude::*;
+
+use std::f32::consts::PI;
+
+use super::{DAMPING_COEFFICIENT, FLUID_DENSITY, MAGNUS_COEFFICIENT, MAX_SPIN, PADDLE_SPIN_FACTOR};
+
+// 球的转动惯量 I = k * m * r^2，网球接近空心球，k = 2/3
+pub const BALL_INERTIA_COEFFICIENT: f32 = 2.0 / 3.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BallState {
+    pub linvel: Vec2,
+    pub angvel: f32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ContactParams {
+    pub normal: Vec2,
+    // 被撞物体在接触点的速度
+    pub other_velocity: Vec2,
+    pub radius: f32,
+    pub restitution: f32,
+    pub friction: f32,
+}
+
+// 速度在法线方向上的镜面反射，restitution 为法向恢复系数
+pub fn reflect(velocity: Vec2, normal: Vec2, restitution: f32) -> Vec2 {
+    let v_n = velocity.dot(normal);
+    velocity - (1.0 + restitution) * v_n * normal
+}
+
+// 计算一次碰撞后球的速度和角速度
+//
+// 法向：按恢复系数反弹。
+// 切向：接触点的滑动速度 = 相对切向速度 - r * ω，摩擦冲量试图让滑动停下，
+// 但大小不超过库仑摩擦的上限 μ * |法向冲量|。摩擦冲量同时改变线速度和角速度，
+// 所以斜着撞墙或撞到移动的砖块都会让球转起来。
+//
+// 球正在离开接触面时不做任何处理，避免同一次碰撞在几个时间步里重复反弹。
+pub fn contact_response(ball: BallState, contact: ContactParams) -> BallState {
+    let normal = contact.normal;
+    let tangent = normal.perp();
+    let v_rel = ball.linvel - contact.other_velocity;
+    let v_n = v_rel.dot(normal);
+    if v_n >= 0.0 {
+        return ball;
+    }
+
+    let j_n = -(1.0 + contact.restitution) * v_n;
+    let slip = v_rel.dot(tangent) - contact.radius * ball.angvel;
+    let j_t = (-slip / (1.0 + 1.0 / BALL_INERTIA_COEFFICIENT))
+        .clamp(-contact.friction * j_n, contact.friction * j_n);
+
+    BallState {
+        linvel: reflect(v_rel, normal, contact.restitution)
+            + contact.other_velocity
+            + j_t * tangent,
+        angvel: ball.angvel - j_t / (BALL_INERTIA_COEFFICIENT * contact.radius),
+    }
+}
+
+// 保持球的速率不变，只保留方向，让球不会越打越慢
+pub fn preserve_speed(velocity: Vec2, speed: f32) -> Vec2 {
+    velocity.normalize_or_zero() * speed
+}
+
+// 旋转按指数衰减，和时间步长无关
+pub fn spin_decay(angvel: f32, delta_secs: f32) -> f32 {
+    angvel * (-DAMPING_COEFFICIENT * delta_secs).exp()
+}
+
+// 砖块横向移动时额外传给球的旋转，摩擦越大（例如胶水）转得越快
+pub fn paddle_spin(paddle_velocity: Vec2, normal: Vec2, friction: f32) -> f32 {
+    -paddle_velocity.dot(normal.perp()) * friction * PADDLE_SPIN_FACTOR
+}
+
+pub fn clamp_spin(angvel: f32) -> f32 {
+    angvel.clamp(-MAX_SPIN, MAX_SPIN)
+}
+
+// 击球位置决定的反弹方向：正中间竖直弹起，越靠边越斜，边缘为 max_angle
+// hit_offset 是球相对砖块中心的横向距离
+pub fn paddle_deflection(hit_offset: f32, half_width: f32, max_angle: f32) -> Vec2 {
+    let angle = (hit_offset / half_width).clamp(-1.0, 1.0) * max_angle;
+    Vec2::new(angle.sin(), angle.cos())
+}
+
+pub fn is_perfect_hit(hit_offset: f32, half_width: f32, window: f32) -> bool {
+    (hit_offset / half_width).abs() <= window
+}
+
+// 马格努斯效应产生的加速度，F = S * ω × v
+// 方向垂直于速度，逆时针旋转（ω > 0）的球向速度的左侧偏
+pub fn magnus_acceleration(linvel: Vec2, angvel: f32, radius: f32) -> Vec2 {
+    FLUID_DENSITY * PI * radius * MAGNUS_COEFFICIENT * 0.001 * angvel * linvel.perp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-4;
+
+    fn wall_contact(friction: f32) -> ContactParams {
+        ContactParams {
+            normal: Vec2::Y,
+            other_velocity: Vec2::ZERO,
+            radius: 20.0,
+            restitution: 1.0,
+            friction,
+        }
+    }
+
+    #[test]
+    fn reflect_flips_normal_component() {
+        let v = reflect(Vec2::new(3.0, -4.0), Vec2::Y, 1.0);
+        assert!(v.abs_diff_eq(Vec2::new(3.0, 4.0), EPSILON));
+    }
+
+    #[test]
+    fn reflect_scales_with_restitution() {
+        let v = reflect(Vec2::new(0.0, -10.0), Vec2::Y, 0.5);
+        assert!(v.abs_diff_eq(Vec2::new(0.0, 5.0), EPSILON));
+    }
+
+    #[test]
+    fn frictionless_elastic_contact_preserves_energy() {
+        let ball = BallState {
+            linvel: Vec2::new(300.0, -400.0),
+            angvel: 2.0,
+        };
+        let after = contact_response(ball, wall_contact(0.0));
+        assert!((after.linvel.length() - ball.linvel.length()).abs() < EPSILON);
+        assert_eq!(after.angvel, ball.angvel);
+    }
+
+    #[test]
+    fn separating_ball_is_untouched() {
+        let ball = BallState {
+            linvel: Vec2::new(100.0, 50.0),
+            angvel: 0.0,
+        };
+        assert_eq!(contact_response(ball, wall_contact(1.0)), ball);
+    }
+
+    #[test]
+    fn friction_never_adds_energy() {
+        let ball = BallState {
+            linvel: Vec2::new(300.0, -400.0),
+            angvel: 0.0,
+        };
+        let after = contact_response(ball, wall_contact(1.0));
+        let inertia = BALL_INERTIA_COEFFICIENT * 20.0 * 20.0;
+        let energy = |s: BallState| s.linvel.length_squared() + inertia * s.angvel * s.angvel;
+        assert!(energy(after) <= energy(ball) + EPSILON);
+    }
+
+    #[test]
+    fn tangential_velocity_transfers_spin() {
+        // 向右斜着撞到地面，摩擦让球顺着前进方向滚动（顺时针，角速度为负）
+        let ball = BallState {
+            linvel: Vec2::new(300.0, -400.0),
+            angvel: 0.0,
+        };
+        let after = contact_response(ball, wall_contact(1.0));
+        assert!(after.angvel < 0.0);
+        assert!(after.linvel.x < ball.linvel.x);
+    }
+
+    #[test]
+    fn moving_surface_transfers_spin() {
+        // 垂直落在向右移动的砖块上
+        let ball = BallState {
+            linvel: Vec2::new(0.0, -400.0),
+            angvel: 0.0,
+        };
+        let contact = ContactParams {
+            other_velocity: Vec2::new(200.0, 0.0),
+            ..wall_contact(1.0)
+        };
+        let after = contact_response(ball, contact);
+        assert!(after.angvel > 0.0);
+        assert!(after.linvel.x > 0.0);
+    }
+
+    #[test]
+    fn rolling_without_slip_keeps_tangential_velocity() {
+        let ball = BallState {
+            linvel: Vec2::new(200.0, -400.0),
+            // 向右滚动时顺时针旋转
+            angvel: -200.0 / 20.0,
+        };
+        let after = contact_response(ball, wall_contact(1.0));
+        assert!((after.linvel.x - ball.linvel.x).abs() < EPSILON);
+        assert!((after.angvel - ball.angvel).abs() < EPSILON);
+    }
+
+    #[test]
+    fn preserve_speed_keeps_direction() {
+        let v = preserve_speed(Vec2::new(3.0, 4.0), 10.0);
+        assert!(v.abs_diff_eq(Vec2::new(6.0, 8.0), EPSILON));
+        assert_eq!(preserve_speed(Vec2::ZERO, 10.0), Vec2::ZERO);
+    }
+
+    #[test]
+    fn spin_decays_towards_zero() {
+        let mut angvel = 10.0;
+        for _ in 0..600 {
+            angvel = spin_decay(angvel, 1.0 / 60.0);
+        }
+        assert!(angvel.abs() < 0.01);
+        assert!(spin_decay(-5.0, 0.1) > -5.0);
+    }
+
+    #[test]
+    fn spin_decay_is_frame_rate_independent() {
+        let one_step = spin_decay(10.0, 1.0 / 30.0);
+        let two_steps = spin_decay(spin_decay(10.0, 1.0 / 60.0), 1.0 / 60.0);
+        assert!((one_step - two_steps).abs() < EPSILON);
+    }
+
+    #[test]
+    fn magnus_is_perpendicular_to_velocity() {
+        for linvel in [
+            Vec2::new(0.0, 500.0),
+            Vec2::new(500.0, 0.0),
+            Vec2::new(-300.0, 400.0),
+        ] {
+            let a = magnus_acceleration(linvel, 5.0, 20.0);
+            assert!(a.dot(linvel).abs() < EPSILON);
+            assert!(a.length() > 0.0);
+        }
+    }
+
+    #[test]
+    fn magnus_curves_towards_spin_side() {
+        // 向上飞、逆时针旋转的球向左偏
+        let a = magnus_acceleration(Vec2::new(0.0, 500.0), 5.0, 20.0);
+        assert!(a.x < 0.0);
+        let b = magnus_acceleration(Vec2::new(0.0, 500.0), -5.0, 20.0);
+        assert!(b.x > 0.0);
+    }
+
+    #[test]
+    fn magnus_scales_with_spin() {
+        let v = Vec2::new(0.0, 500.0);
+        let a = magnus_acceleration(v, 2.0, 20.0);
+        let b = magnus_acceleration(v, 4.0, 20.0);
+        assert!(b.abs_diff_eq(a * 2.0, EPSILON));
+    }
+
+    #[test]
+    fn paddle_spin_follows_paddle_and_friction() {
+        // 砖块向右移动时球逆时针旋转，和摩擦带来的旋转方向一致
+        let right = paddle_spin(Vec2::new(200.0, 0.0), Vec2::Y, 1.0);
+        assert!(right > 0.0);
+        assert!(paddle_spin(Vec2::new(-200.0, 0.0), Vec2::Y, 1.0) < 0.0);
+        assert!(paddle_spin(Vec2::new(200.0, 0.0), Vec2::Y, 2.0) > right);
+        assert_eq!(paddle_spin(Vec2::new(0.0, 200.0), Vec2::Y, 1.0), 0.0);
+        assert_eq!(clamp_spin(1000.0), MAX_SPIN);
+    }
+
+    #[test]
+    fn centre_hit_bounces_straight_up() {
+        let dir = paddle_deflection(0.0, 50.0, 1.0);
+        assert!(dir.abs_diff_eq(Vec2::Y, EPSILON));
+    }
+
+    #[test]
+    fn edge_hit_uses_max_angle() {
+        let max_angle = 60f32.to_radians();
+        let right = paddle_deflection(50.0, 50.0, max_angle);
+        let left = paddle_deflection(-50.0, 50.0, max_angle);
+        assert!((right.angle_to(Vec2::Y).abs() - max_angle).abs() < EPSILON);
+        assert!(right.x > 0.0 && left.x < 0.0);
+        assert!((right.x + left.x).abs() < EPSILON);
+        // 超出砖块边缘的接触按边缘处理
+        assert!(paddle_deflection(80.0, 50.0, max_angle).abs_diff_eq(right, EPSILON));
+    }
+
+    #[test]
+    fn deflection_is_monotonic_and_normalized() {
+        let max_angle = 60f32.to_radians();
+        let mut last = -1.0;
+        for offset in [-50.0, -25.0, 0.0, 25.0, 50.0] {
+            let dir = paddle_deflection(offset, 50.0, max_angle);
+            assert!((dir.length() - 1.0).abs() < EPSILON);
+            assert!(dir.x > last);
+            last = dir.x;
+        }
+    }
+
+    #[test]
+    fn perfect_hit_window() {
+        assert!(is_perfect_hit(4.0, 50.0, 0.1));
+        assert!(is_perfect_hit(-5.0, 50.0, 0.1));
+        assert!(!is_perfect_hit(6.0, 50.0, 0.1));
+    }
+
+    #[test]
+    fn no_spin_no_magnus() {
+        assert_eq!(
+            magnus_acceleration(Vec2::new(0.0, 500.0), 0.0, 20.0),
+            Vec2::ZERO
+        );
+    }
+}
@@ -1,7 +1,10 @@
+pub mod abilities;
 pub mod brick_ball;
+pub mod character;
 pub mod input;
 pub mod inventory;
 pub mod item_collection;
+pub mod player;
 pub mod spawn;
 pub mod stats;
 
@@ -10,11 +13,24 @@ mod collision;
 mod movement;
 mod shoot;
 
-pub use shoot::BallInHand;
+pub use shoot::{BallInHand, ShotBy};
 
 use bevy::prelude::*;
 
 #[derive(Component)]
+#[require(
+    abilities::AbilityStats,
+    abilities::Dash,
+    abilities::ChargeShot,
+    stats::BaseStats,
+    stats::StatModifiers,
+    stats::BallDamage,
+    stats::Grip,
+    input::PlayerInput,
+    inventory::Inventory,
+    crate::ui::AimAngle,
+    crate::item::effects::Shield
+)]
 pub struct Brick;
 
 #[derive(Component)]
@@ -32,6 +48,8 @@ impl Plugin for BrickPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
             input::InputPlugin,
+            player::PlayerPlugin,
+            character::CharacterPlugin,
             movement::MovementPlugin,
             spawn::SpawnPlugin,
             shoot::ShootPlugin,
@@ -39,7 +57,7 @@ impl Plugin for BrickPlugin {
             collision::CollisionPlugin,
             animation::AnimationPlugin,
             stats::StatsPlugin,
-            inventory::InventoryPlugin,
+            abilities::AbilitiesPlugin,
             item_collection::ItemCollectionPlugin,
         ));
     }
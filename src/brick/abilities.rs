@@ -0,0 +1,175 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::GameState;
+
+use super::{input::PlayerInput, Brick};
+
+pub struct AbilitiesPlugin;
+
+impl Plugin for AbilitiesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (start_dash, tick_dash, charge_shot, dash_blink).run_if(in_state(GameState::Gaming)),
+        );
+    }
+}
+
+// 砖块技能的属性，道具可以在拾取时修改
+#[derive(Component, Debug, Clone)]
+pub struct AbilityStats {
+    pub dash_speed: f32,
+    pub dash_duration: f32,
+    pub dash_cooldown: f32,
+    // 冲刺开始后的无敌时间，期间不受敌人的接触伤害
+    pub invulnerable_duration: f32,
+    // 蓄满力需要的时间
+    pub charge_time: f32,
+    pub charge_cooldown: f32,
+    // 蓄满力时出手速度和伤害系数的倍率
+    pub max_charge_speed: f32,
+    pub max_charge_damage: f32,
+}
+
+impl Default for AbilityStats {
+    fn default() -> Self {
+        Self {
+            dash_speed: 1400.0,
+            dash_duration: 0.15,
+            dash_cooldown: 1.2,
+            invulnerable_duration: 0.3,
+            charge_time: 1.0,
+            charge_cooldown: 2.0,
+            max_charge_speed: 1.8,
+            max_charge_damage: 2.0,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Dash {
+    pub direction: Vec2,
+    active: Timer,
+    invulnerable: Timer,
+    cooldown: Timer,
+}
+
+impl Default for Dash {
+    fn default() -> Self {
+        Self {
+            direction: Vec2::ZERO,
+            active: finished_timer(),
+            invulnerable: finished_timer(),
+            cooldown: finished_timer(),
+        }
+    }
+}
+
+impl Dash {
+    pub fn is_dashing(&self) -> bool {
+        !self.active.finished()
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        !self.invulnerable.finished()
+    }
+
+    // 冷却进度，1.0 表示可以使用
+    pub fn cooldown_fraction(&self) -> f32 {
+        self.cooldown.fraction()
+    }
+}
+
+#[derive(Component)]
+pub struct ChargeShot {
+    // 当前蓄力程度，0.0 ~ 1.0
+    pub ratio: f32,
+    cooldown: Timer,
+}
+
+impl Default for ChargeShot {
+    fn default() -> Self {
+        Self {
+            ratio: 0.0,
+            cooldown: finished_timer(),
+        }
+    }
+}
+
+impl ChargeShot {
+    pub fn cooldown_fraction(&self) -> f32 {
+        self.cooldown.fraction()
+    }
+
+    // 出手时结算蓄力，返回速度倍率和伤害系数；蓄过力才进入冷却
+    pub fn release(&mut self, stats: &AbilityStats) -> (f32, f32) {
+        let ratio = self.ratio;
+        self.ratio = 0.0;
+        if ratio > 0.0 {
+            self.cooldown = Timer::from_seconds(stats.charge_cooldown, TimerMode::Once);
+        }
+        (
+            1.0 + (stats.max_charge_speed - 1.0) * ratio,
+            1.0 + (stats.max_charge_damage - 1.0) * ratio,
+        )
+    }
+}
+
+// 时长为零并且已经结束的计时器，表示技能一开始就可以使用
+fn finished_timer() -> Timer {
+    let mut timer = Timer::from_seconds(0.0, TimerMode::Once);
+    timer.tick(Duration::ZERO);
+    timer
+}
+
+fn start_dash(mut q_brick: Query<(&mut PlayerInput, &mut Dash, &AbilityStats), With<Brick>>) {
+    for (mut player_input, mut dash, stats) in q_brick.iter_mut() {
+        if !player_input.dash {
+            continue;
+        }
+        player_input.dash = false;
+        // 没有移动方向时不冲刺
+        if !dash.cooldown.finished() || player_input.move_direction == Vec2::ZERO {
+            continue;
+        }
+        dash.direction = player_input.move_direction;
+        dash.active = Timer::from_seconds(stats.dash_duration, TimerMode::Once);
+        dash.invulnerable = Timer::from_seconds(stats.invulnerable_duration, TimerMode::Once);
+        dash.cooldown = Timer::from_seconds(stats.dash_cooldown, TimerMode::Once);
+    }
+}
+
+fn tick_dash(mut q_dash: Query<&mut Dash, With<Brick>>, time: Res<Time>) {
+    for mut dash in q_dash.iter_mut() {
+        dash.active.tick(time.delta());
+        dash.invulnerable.tick(time.delta());
+        dash.cooldown.tick(time.delta());
+    }
+}
+
+// 瞄准时按住射击键蓄力，冷却中不能蓄力
+fn charge_shot(
+    mut q_brick: Query<(&PlayerInput, &mut ChargeShot, &AbilityStats), With<Brick>>,
+    time: Res<Time>,
+) {
+    for (player_input, mut charge, stats) in q_brick.iter_mut() {
+        charge.cooldown.tick(time.delta());
+        if !player_input.toggle_aim {
+            charge.ratio = 0.0;
+            continue;
+        }
+        if player_input.charging && charge.cooldown.finished() {
+            charge.ratio = (charge.ratio + time.delta_secs() / stats.charge_time).min(1.0);
+        }
+    }
+}
+
+// 无敌期间砖块半透明
+fn dash_blink(mut q_brick: Query<(&Dash, &mut Sprite), With<Brick>>) {
+    for (dash, mut sprite) in q_brick.iter_mut() {
+        let alpha = if dash.is_invulnerable() { 0.5 } else { 1.0 };
+        sprite.color.set_alpha(alpha);
+    }
+}
@@ -5,7 +5,11 @@ use crate::{
     GameState,
 };
 
-use super::{stats::Pressure, Brick};
+use super::{
+    character::{Character, CharacterRegistry},
+    stats::Pressure,
+    Brick,
+};
 
 pub struct AnimationPlugin;
 
@@ -18,20 +22,23 @@ impl Plugin for AnimationPlugin {
     }
 }
 
+// 每个角色的压力阶段和动画帧在 characters.ron 里定义
 fn change_pressure_animation(
     mut q_brick: Query<
-        (&Pressure, &mut AnimationIndices, &mut AnimSprite),
+        (
+            &Character,
+            &Pressure,
+            &mut AnimationIndices,
+            &mut AnimSprite,
+        ),
         (With<Brick>, Changed<Pressure>),
     >,
+    characters: Res<CharacterRegistry>,
 ) {
-    for (pressure, mut idices, mut anim_sprite) in q_brick.iter_mut() {
+    for (&character, pressure, mut idices, mut anim_sprite) in q_brick.iter_mut() {
         anim_sprite.disabled = true;
-        *idices = match pressure.current {
-            0.0..20.0 => AnimationIndices::new(0, 0),
-            20.0..40.0 => AnimationIndices::new(1, 1),
-            40.0..60.0 => AnimationIndices::new(2, 2),
-            60.0..80.0 => AnimationIndices::new(3, 6),
-            _ => AnimationIndices::new(7, 10),
-        };
+        *idices = characters
+            .get(character)
+            .pressure_animation(pressure.current / pressure.max);
     }
 }
@@ -2,7 +2,7 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
 use crate::{
-    ball::{Ball, DamageCoefficient, TargetForce},
+    ball::{Ball, DamageCoefficient},
     collision_group::{GROUP_BALL, GROUP_TRANSPARANT_WALL},
     events::Damage,
     world::map::room::loading::RoomComponents,
@@ -13,6 +13,7 @@ use crate::{
 pub const BRICK_BALL_SIZE: f32 = 40.0;
 pub const BRICK_BALL_MASS: f32 = 1.0;
 pub const BRICK_BALL_ANGULAR_INERTIAL: f32 = 0.3;
+pub const BALL_DAMAGE: f32 = 10.0;
 
 pub struct BrickBallPlugin;
 
@@ -44,12 +45,11 @@ pub fn spawn_ball(
                 sleeping: is_sleeping,
                 ..default()
             },
-            Damage { value: 10.0 },
+            Damage { value: BALL_DAMAGE },
             Transform::from_translation(position.extend(1.0)),
             ball_type,
             RoomComponents,
             DamageCoefficient(1.0),
-            TargetForce::default(),
         ))
         .id();
 
@@ -0,0 +1,226 @@
+use std::fs::File;
+
+use bevy::prelude::*;
+use ron::de::from_reader;
+use serde::Deserialize;
+
+use crate::{ball::Ball, item::Item, utils::anim_sprite::AnimationIndices};
+
+use super::{
+    player::Player,
+    stats::{BaseStats, BRICK_GRIP},
+};
+
+pub struct CharacterPlugin;
+
+impl Plugin for CharacterPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CharacterRegistry>()
+            .init_resource::<SelectedCharacters>()
+            .add_systems(Startup, init_characters);
+    }
+}
+
+// 角色在注册表里的编号，角色的数据都在 characters.ron 中
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[require(Passives)]
+pub struct Character(usize);
+
+// 角色的被动道具，不放进共享的道具收藏，只对这个角色的砖块生效
+#[derive(Component, Default)]
+pub struct Passives(pub Vec<Item>);
+
+// 本局每个玩家使用的角色，在选择角色界面设置
+#[derive(Resource, Default)]
+pub struct SelectedCharacters([Character; 2]);
+
+impl SelectedCharacters {
+    pub fn get(&self, player: Player) -> Character {
+        self.0[player.index()]
+    }
+
+    pub fn set(&mut self, player: Player, character: Character) {
+        self.0[player.index()] = character;
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct SpriteSheet {
+    pub path: String,
+    pub tile_size: (u32, u32),
+    pub columns: u32,
+    pub rows: u32,
+}
+
+// 压力比例达到 from 之后播放 first 到 last 帧
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct PressureStage {
+    pub from: f32,
+    pub first: usize,
+    pub last: usize,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct StartingInventory {
+    pub capacity: usize,
+    pub balls: Vec<Ball>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CharacterDef {
+    pub id: String,
+    // 名称和描述是本地化文本的键
+    pub name: String,
+    pub description: String,
+    pub sprite: SpriteSheet,
+    // 没有专门美术资源的角色可以复用其他角色的图，再染上颜色
+    #[serde(default)]
+    pub tint: Option<(f32, f32, f32)>,
+    // 按 from 从小到大排列
+    pub pressure_stages: Vec<PressureStage>,
+    pub width: f32,
+    pub height: f32,
+    pub speed: f32,
+    pub mass: f32,
+    pub friction: f32,
+    pub restitution: f32,
+    pub max_pressure: f32,
+    #[serde(default = "default_grip")]
+    pub grip: f32,
+    pub inventory: StartingInventory,
+    // 角色独有的被动，是一个不会出现在道具池里的道具
+    #[serde(default)]
+    pub passive: Option<String>,
+}
+
+fn default_grip() -> f32 {
+    BRICK_GRIP
+}
+
+impl CharacterDef {
+    pub fn tint_color(&self) -> Color {
+        self.tint
+            .map_or(Color::WHITE, |(r, g, b)| Color::srgb(r, g, b))
+    }
+
+    // 当前压力比例对应的动画帧
+    pub fn pressure_animation(&self, ratio: f32) -> AnimationIndices {
+        self.pressure_stages
+            .iter()
+            .rev()
+            .find(|stage| ratio >= stage.from)
+            .or(self.pressure_stages.first())
+            .map_or(AnimationIndices::new(0, 0), |stage| {
+                AnimationIndices::new(stage.first, stage.last)
+            })
+    }
+
+    // 道具修正在这些基础属性上计算
+    pub fn base_stats(&self) -> BaseStats {
+        BaseStats {
+            speed: self.speed,
+            friction: self.friction,
+            max_pressure: self.max_pressure,
+            width: self.width,
+            height: self.height,
+            grip: self.grip,
+            ..default()
+        }
+    }
+}
+
+// 所有角色的定义，启动时从 characters.ron 读取
+#[derive(Resource, Default)]
+pub struct CharacterRegistry {
+    defs: Vec<CharacterDef>,
+    textures: Vec<Handle<Image>>,
+    layouts: Vec<Handle<TextureAtlasLayout>>,
+}
+
+impl CharacterRegistry {
+    pub fn get(&self, character: Character) -> &CharacterDef {
+        &self.defs[character.0]
+    }
+
+    pub fn find(&self, id: &str) -> Option<Character> {
+        self.defs.iter().position(|def| def.id == id).map(Character)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Character, &CharacterDef)> {
+        self.defs
+            .iter()
+            .enumerate()
+            .map(|(index, def)| (Character(index), def))
+    }
+
+    // 图块按角色的尺寸拉伸，和碰撞体一样大
+    pub fn sprite(&self, character: Character, index: usize) -> Sprite {
+        let def = self.get(character);
+        Sprite {
+            color: def.tint_color(),
+            custom_size: Some(Vec2::new(def.width, def.height)),
+            ..Sprite::from_atlas_image(
+                self.textures[character.0].clone(),
+                TextureAtlas {
+                    layout: self.layouts[character.0].clone(),
+                    index,
+                },
+            )
+        }
+    }
+
+    // 界面上显示的角色图像
+    pub fn image_node(&self, character: Character) -> ImageNode {
+        ImageNode::from_atlas_image(
+            self.textures[character.0].clone(),
+            TextureAtlas {
+                layout: self.layouts[character.0].clone(),
+                index: 0,
+            },
+        )
+        .with_color(self.get(character).tint_color())
+    }
+}
+
+// characters.ron 的内容
+#[derive(Deserialize)]
+struct CharacterCatalogue {
+    characters: Vec<CharacterDef>,
+}
+
+fn init_characters(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    let file = File::open("assets/bricks/characters.ron").expect("Failed to open characters.ron");
+    let catalogue: CharacterCatalogue = from_reader(file).expect("Unable to load characters.ron");
+    if catalogue.characters.is_empty() {
+        panic!("characters.ron needs at least one character");
+    }
+
+    let textures = catalogue
+        .characters
+        .iter()
+        .map(|def| asset_server.load(def.sprite.path.clone()))
+        .collect();
+    let layouts = catalogue
+        .characters
+        .iter()
+        .map(|def| {
+            let (width, height) = def.sprite.tile_size;
+            layouts.add(TextureAtlasLayout::from_grid(
+                UVec2::new(width, height),
+                def.sprite.columns,
+                def.sprite.rows,
+                None,
+                None,
+            ))
+        })
+        .collect();
+    commands.insert_resource(CharacterRegistry {
+        defs: catalogue.characters,
+        textures,
+        layouts,
+    });
+}
@@ -2,9 +2,11 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
 use crate::ball::Ball;
+use crate::enemy::Enemy;
+use crate::item::ItemRegistry;
 use crate::GameState;
 
-use super::Brick;
+use super::{item_collection::ItemCollection, Brick};
 
 pub struct CollisionPlugin;
 
@@ -20,44 +22,47 @@ fn brick_collision_handle(
     rapier_contex: ReadRapierContext,
     mut q_brick: Query<(&Children, &mut Transform), With<Brick>>,
     q_ball: Query<(), With<Ball>>,
+    q_enemy: Query<(), With<Enemy>>,
     q_parent: Query<&Parent, With<Collider>>,
+    collection: Res<ItemCollection>,
+    registry: Res<ItemRegistry>,
 ) {
-    let (brick_children, mut brick_transform) = q_brick.get_single_mut().ok().unwrap();
-    let child_entity = brick_children[0];
+    let has_wheel = registry
+        .find("wheel")
+        .is_some_and(|wheel| collection.contains(wheel));
+    let rapier_contex = rapier_contex.single();
 
-    for contact_pair in rapier_contex
-        .single()
-        .contact_pairs_with(child_entity)
-        // 过滤掉所有Ball
-        // TODO: 如果没有Wheel物品把Enemy也给过滤掉
-        .filter(|contact_pair| {
-            let collider_parent = q_parent.get(contact_pair.collider2()).ok().unwrap().get();
-            if let Ok(_) = q_ball.get(collider_parent) {
-                false
+    for (brick_children, mut brick_transform) in q_brick.iter_mut() {
+        let child_entity = brick_children[0];
+        for contact_pair in rapier_contex
+            .contact_pairs_with(child_entity)
+            // 过滤掉所有Ball，如果没有Wheel物品把Enemy也给过滤掉
+            .filter(|contact_pair| {
+                let collider_parent = q_parent.get(contact_pair.collider2()).ok().unwrap().get();
+                !(q_ball.contains(collider_parent)
+                    || (!has_wheel && q_enemy.contains(collider_parent)))
+            })
+        {
+            if child_entity == contact_pair.collider1() {
+                for manifold in contact_pair.manifolds() {
+                    // println!("brick cotact!!!");
+                    let distance = if let Some(cv) = manifold.point(0) {
+                        cv.dist().abs()
+                    } else {
+                        0.0
+                    };
+                    brick_transform.translation += distance * manifold.local_n2().extend(1.0);
+                }
             } else {
-                true
-            }
-        })
-    {
-        if child_entity == contact_pair.collider1() {
-            for manifold in contact_pair.manifolds() {
-                // println!("brick cotact!!!");
-                let distance = if let Some(cv) = manifold.point(0) {
-                    cv.dist().abs()
-                } else {
-                    0.0
-                };
-                brick_transform.translation += distance * manifold.local_n2().extend(1.0);
-            }
-        } else {
-            for manifold in contact_pair.manifolds() {
-                // println!("brick cotact!!!");
-                let distance = if let Some(cv) = manifold.point(0) {
-                    cv.dist().abs()
-                } else {
-                    0.0
-                };
-                brick_transform.translation += distance * manifold.local_n1().extend(1.0);
+                for manifold in contact_pair.manifolds() {
+                    // println!("brick cotact!!!");
+                    let distance = if let Some(cv) = manifold.point(0) {
+                        cv.dist().abs()
+                    } else {
+                        0.0
+                    };
+                    brick_transform.translation += distance * manifold.local_n1().extend(1.0);
+                }
             }
         }
     }
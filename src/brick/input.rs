@@ -1,8 +1,9 @@
-use bevy::{input::InputSystem, prelude::*};
+use bevy::{ecs::system::SystemParam, input::InputSystem, prelude::*};
+use serde::{Deserialize, Serialize};
 
 use crate::GameState;
 
-use super::inventory::Inventory;
+use super::{inventory::Inventory, player::Player};
 
 // #[derive(Resource, Default)]
 // pub struct MouseWorldCoords(pub Vec2);
@@ -15,17 +16,237 @@ pub enum ChooseRoom {
     Enter,
 }
 
-#[derive(Resource, Default)]
+// 每个砖块自己的输入状态
+#[derive(Component, Default)]
 pub struct PlayerInput {
     pub move_direction: Vec2,
     pub toggle_aim: bool,
     pub shoot: bool,
+    // 瞄准时按住射击键蓄力，松开出手
+    pub charging: bool,
+    pub dash: bool,
+    pub use_item: bool,
     pub aim_arrow_rotation: f32,
     pub choose_room: ChooseRoom,
 }
 
 #[derive(Event)]
-pub struct ToggleAimEvent(pub bool);
+pub struct ToggleAimEvent {
+    pub brick: Entity,
+    pub aiming: bool,
+}
+
+// 砖块使用的输入设备
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputDevice {
+    // 使用对应玩家的按键绑定
+    Keyboard,
+    Gamepad(Entity),
+}
+
+// 按键绑定，由设置文件读取和保存
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub move_up: KeyCode,
+    pub move_down: KeyCode,
+    pub move_left: KeyCode,
+    pub move_right: KeyCode,
+    pub toggle_aim: KeyCode,
+    pub shoot: KeyCode,
+    pub dash: KeyCode,
+    pub stats_panel: KeyCode,
+    pub use_item: KeyCode,
+    // 跳过当前的通知
+    pub skip_notification: KeyCode,
+    // 瞄准时旋转箭头，选择房间时切换房间
+    pub left: KeyCode,
+    pub right: KeyCode,
+    pub enter: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            move_up: KeyCode::KeyW,
+            move_down: KeyCode::KeyS,
+            move_left: KeyCode::KeyA,
+            move_right: KeyCode::KeyD,
+            toggle_aim: KeyCode::ShiftLeft,
+            shoot: KeyCode::ArrowUp,
+            dash: KeyCode::Space,
+            stats_panel: KeyCode::Tab,
+            use_item: KeyCode::KeyQ,
+            skip_notification: KeyCode::KeyE,
+            left: KeyCode::ArrowLeft,
+            right: KeyCode::ArrowRight,
+            enter: KeyCode::Enter,
+        }
+    }
+}
+
+impl KeyBindings {
+    // 双人时第二个玩家的默认键位，避开第一个玩家的按键
+    pub fn player_two() -> Self {
+        Self {
+            move_up: KeyCode::KeyI,
+            move_down: KeyCode::KeyK,
+            move_left: KeyCode::KeyJ,
+            move_right: KeyCode::KeyL,
+            toggle_aim: KeyCode::KeyU,
+            shoot: KeyCode::KeyO,
+            dash: KeyCode::KeyH,
+            use_item: KeyCode::KeyY,
+            left: KeyCode::KeyN,
+            right: KeyCode::KeyM,
+            enter: KeyCode::Comma,
+            ..default()
+        }
+    }
+
+    fn key(&self, action: Action) -> KeyCode {
+        match action {
+            Action::ToggleAim => self.toggle_aim,
+            Action::Shoot => self.shoot,
+            Action::Dash => self.dash,
+            Action::UseItem => self.use_item,
+            Action::Left => self.left,
+            Action::Right => self.right,
+            Action::Enter => self.enter,
+        }
+    }
+}
+
+// 第二个玩家的按键绑定，统计面板和跳过通知只用第一个玩家的按键
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(transparent)]
+pub struct PlayerTwoBindings(pub KeyBindings);
+
+impl Default for PlayerTwoBindings {
+    fn default() -> Self {
+        Self(KeyBindings::player_two())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Action {
+    ToggleAim,
+    Shoot,
+    Dash,
+    UseItem,
+    Left,
+    Right,
+    Enter,
+}
+
+impl Action {
+    fn button(self) -> GamepadButton {
+        match self {
+            Action::ToggleAim => GamepadButton::RightTrigger,
+            Action::Shoot => GamepadButton::South,
+            Action::Dash => GamepadButton::East,
+            Action::UseItem => GamepadButton::North,
+            Action::Left => GamepadButton::DPadLeft,
+            Action::Right => GamepadButton::DPadRight,
+            Action::Enter => GamepadButton::Start,
+        }
+    }
+}
+
+// 按玩家的输入设备读取按键，键盘按各自的绑定，手柄的按键是固定的
+#[derive(SystemParam)]
+struct Devices<'w, 's> {
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    bindings: Res<'w, KeyBindings>,
+    player_two: Res<'w, PlayerTwoBindings>,
+    gamepads: Query<'w, 's, &'static Gamepad>,
+}
+
+impl Devices<'_, '_> {
+    fn bindings(&self, player: Player) -> &KeyBindings {
+        match player {
+            Player::One => &self.bindings,
+            Player::Two => &self.player_two.0,
+        }
+    }
+
+    fn check(
+        &self,
+        player: Player,
+        device: InputDevice,
+        action: Action,
+        key: fn(&ButtonInput<KeyCode>, KeyCode) -> bool,
+        button: fn(&Gamepad, GamepadButton) -> bool,
+    ) -> bool {
+        match device {
+            InputDevice::Keyboard => key(&self.keys, self.bindings(player).key(action)),
+            InputDevice::Gamepad(entity) => self
+                .gamepads
+                .get(entity)
+                .is_ok_and(|gamepad| button(gamepad, action.button())),
+        }
+    }
+
+    fn pressed(&self, player: Player, device: InputDevice, action: Action) -> bool {
+        self.check(
+            player,
+            device,
+            action,
+            ButtonInput::pressed,
+            Gamepad::pressed,
+        )
+    }
+
+    fn just_pressed(&self, player: Player, device: InputDevice, action: Action) -> bool {
+        self.check(
+            player,
+            device,
+            action,
+            ButtonInput::just_pressed,
+            Gamepad::just_pressed,
+        )
+    }
+
+    fn just_released(&self, player: Player, device: InputDevice, action: Action) -> bool {
+        self.check(
+            player,
+            device,
+            action,
+            ButtonInput::just_released,
+            Gamepad::just_released,
+        )
+    }
+
+    fn movement(&self, player: Player, device: InputDevice) -> Vec2 {
+        match device {
+            InputDevice::Keyboard => {
+                let bindings = self.bindings(player);
+                let mut direction = Vec2::ZERO;
+                if self.keys.pressed(bindings.move_up) {
+                    direction += Vec2::new(0.0, 1.0);
+                }
+
+                if self.keys.pressed(bindings.move_down) {
+                    direction += Vec2::new(0.0, -1.0);
+                }
+                if self.keys.pressed(bindings.move_left) {
+                    direction += Vec2::new(-1.0, 0.0);
+                }
+
+                if self.keys.pressed(bindings.move_right) {
+                    direction += Vec2::new(1.0, 0.0);
+                }
+                direction.normalize_or_zero()
+            }
+            // 摇杆推一半就是一半的速度
+            InputDevice::Gamepad(entity) => {
+                self.gamepads.get(entity).map_or(Vec2::ZERO, |gamepad| {
+                    gamepad.left_stick().clamp_length_max(1.0)
+                })
+            }
+        }
+    }
+}
 
 pub struct InputPlugin;
 
@@ -38,34 +259,24 @@ impl Plugin for InputPlugin {
                 aim_arrow_rotation,
                 toggle_aim,
                 shoot,
+                dash,
+                use_item,
                 choose_room,
             )
                 .after(InputSystem)
                 .run_if(in_state(GameState::Gaming)),
         )
-        .init_resource::<PlayerInput>()
         .add_event::<ToggleAimEvent>();
     }
 }
 
-fn player_movement(keys: Res<ButtonInput<KeyCode>>, mut player_input: ResMut<PlayerInput>) {
-    let mut direction = Vec2::ZERO;
-    if keys.pressed(KeyCode::KeyW) {
-        direction += Vec2::new(0.0, 1.0);
-    }
-
-    if keys.pressed(KeyCode::KeyS) {
-        direction += Vec2::new(0.0, -1.0);
-    }
-    if keys.pressed(KeyCode::KeyA) {
-        direction += Vec2::new(-1.0, 0.0);
-    }
-
-    if keys.pressed(KeyCode::KeyD) {
-        direction += Vec2::new(1.0, 0.0);
+fn player_movement(
+    devices: Devices,
+    mut q_input: Query<(&Player, &InputDevice, &mut PlayerInput)>,
+) {
+    for (&player, &device, mut player_input) in q_input.iter_mut() {
+        player_input.move_direction = devices.movement(player, device);
     }
-
-    player_input.move_direction = direction.normalize_or_zero();
 }
 
 // pub fn fetch_mouse_world_coords(
@@ -85,55 +296,81 @@ fn player_movement(keys: Res<ButtonInput<KeyCode>>, mut player_input: ResMut<Pla
 // }
 
 fn toggle_aim(
-    keys: Res<ButtonInput<KeyCode>>,
-    mut player_input: ResMut<PlayerInput>,
+    devices: Devices,
+    mut q_input: Query<(Entity, &Player, &InputDevice, &mut PlayerInput, &Inventory)>,
     mut events: EventWriter<ToggleAimEvent>,
-    inventory: Res<Inventory>,
 ) {
-    if inventory.index() == 0 {
-        return;
-    }
-    if keys.just_pressed(KeyCode::ShiftLeft) {
-        player_input.toggle_aim = !player_input.toggle_aim;
-        if player_input.toggle_aim {
-            events.send(ToggleAimEvent(true));
-        } else {
-            events.send(ToggleAimEvent(false));
+    for (brick, &player, &device, mut player_input, inventory) in q_input.iter_mut() {
+        if inventory.index() == 0 {
+            continue;
+        }
+        if devices.just_pressed(player, device, Action::ToggleAim) {
+            player_input.toggle_aim = !player_input.toggle_aim;
+            events.send(ToggleAimEvent {
+                brick,
+                aiming: player_input.toggle_aim,
+            });
         }
     }
 }
 
-fn shoot(keys: Res<ButtonInput<KeyCode>>, mut player_input: ResMut<PlayerInput>) {
-    player_input.shoot = player_input.toggle_aim && keys.just_pressed(KeyCode::ArrowUp);
+fn shoot(devices: Devices, mut q_input: Query<(&Player, &InputDevice, &mut PlayerInput)>) {
+    for (&player, &device, mut player_input) in q_input.iter_mut() {
+        player_input.charging =
+            player_input.toggle_aim && devices.pressed(player, device, Action::Shoot);
+        player_input.shoot =
+            player_input.toggle_aim && devices.just_released(player, device, Action::Shoot);
+    }
 }
 
-fn aim_arrow_rotation(keys: Res<ButtonInput<KeyCode>>, mut player_input: ResMut<PlayerInput>) {
-    let mut rotation: f32 = 0.0;
-    if keys.pressed(KeyCode::ArrowLeft) {
-        rotation += 1.0;
-    };
-    if keys.pressed(KeyCode::ArrowRight) {
-        rotation -= 1.0;
-    };
-    player_input.aim_arrow_rotation = rotation;
+// 冲刺请求由技能系统消费
+fn dash(devices: Devices, mut q_input: Query<(&Player, &InputDevice, &mut PlayerInput)>) {
+    for (&player, &device, mut player_input) in q_input.iter_mut() {
+        if devices.just_pressed(player, device, Action::Dash) {
+            player_input.dash = true;
+        }
+    }
 }
 
-fn choose_room(keys: Res<ButtonInput<KeyCode>>, mut player_input: ResMut<PlayerInput>) {
-    if player_input.toggle_aim {
-        return;
+fn use_item(devices: Devices, mut q_input: Query<(&Player, &InputDevice, &mut PlayerInput)>) {
+    for (&player, &device, mut player_input) in q_input.iter_mut() {
+        player_input.use_item = devices.just_pressed(player, device, Action::UseItem);
     }
-    let mut cr = ChooseRoom::Idle;
-    if keys.just_pressed(KeyCode::ArrowLeft) {
-        cr = ChooseRoom::Left;
-    }
-    if keys.just_pressed(KeyCode::ArrowRight) {
-        cr = ChooseRoom::Right;
+}
+
+fn aim_arrow_rotation(
+    devices: Devices,
+    mut q_input: Query<(&Player, &InputDevice, &mut PlayerInput)>,
+) {
+    for (&player, &device, mut player_input) in q_input.iter_mut() {
+        let mut rotation: f32 = 0.0;
+        if devices.pressed(player, device, Action::Left) {
+            rotation += 1.0;
+        };
+        if devices.pressed(player, device, Action::Right) {
+            rotation -= 1.0;
+        };
+        player_input.aim_arrow_rotation = rotation;
     }
-    if keys.just_pressed(KeyCode::Enter) {
-        cr = ChooseRoom::Enter;
+}
+
+fn choose_room(devices: Devices, mut q_input: Query<(&Player, &InputDevice, &mut PlayerInput)>) {
+    for (&player, &device, mut player_input) in q_input.iter_mut() {
+        if player_input.toggle_aim {
+            continue;
+        }
+        let mut cr = ChooseRoom::Idle;
+        if devices.just_pressed(player, device, Action::Left) {
+            cr = ChooseRoom::Left;
+        }
+        if devices.just_pressed(player, device, Action::Right) {
+            cr = ChooseRoom::Right;
+        }
+        if devices.just_pressed(player, device, Action::Enter) {
+            cr = ChooseRoom::Enter;
+        }
+        player_input.choose_room = cr;
     }
-    // cr = ChooseRoom::Idle;
-    player_input.choose_room = cr;
 }
 
 // fn reset_player_input(mut player_input: ResMut<PlayerInput>) {
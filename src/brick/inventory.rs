@@ -4,18 +4,8 @@ use bevy::prelude::*;
 
 use crate::ball::Ball;
 
-pub struct InventoryPlugin;
-
-impl Plugin for InventoryPlugin {
-    fn build(&self, app: &mut App) {
-        app.insert_resource(Inventory::new(
-            6,
-            &[Ball::Tennis, Ball::Tennis, Ball::Tennis],
-        ));
-    }
-}
-
-#[derive(Resource)]
+// 每个砖块自己的背包，开局时按角色和初始配置设置
+#[derive(Component)]
 pub struct Inventory {
     capacity: usize,
     index: usize, // 从1开始
@@ -95,6 +85,8 @@ impl Inventory {
         // println!("{:#?}", self.balls);
     }
 
+    // 目前只有开发者工具会缩小背包
+    #[cfg_attr(not(feature = "dev-tools"), allow(dead_code))]
     pub fn redusing(&mut self, num: usize) {
         if self.capacity == 0 {
             return;
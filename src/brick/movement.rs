@@ -3,7 +3,12 @@ use bevy_rapier2d::prelude::Velocity;
 
 use crate::GameState;
 
-use super::{input::PlayerInput, stats::*, Brick, Speed};
+use super::{
+    abilities::{AbilityStats, Dash},
+    input::PlayerInput,
+    stats::*,
+    Brick, Speed,
+};
 
 pub struct MovementPlugin;
 
@@ -17,10 +22,28 @@ impl Plugin for MovementPlugin {
 }
 
 fn brick_movement(
-    mut brick_velocity: Single<&mut Velocity, With<Brick>>,
-    brick_speed: Single<&Speed, With<Brick>>,
-    player_input: Res<PlayerInput>,
+    mut q_brick: Query<
+        (
+            &mut Velocity,
+            &Speed,
+            &Grip,
+            &Dash,
+            &AbilityStats,
+            &PlayerInput,
+        ),
+        With<Brick>,
+    >,
 ) {
+    for (mut brick_velocity, speed, grip, dash, stats, player_input) in q_brick.iter_mut() {
+        // 冲刺期间保持冲刺速度，不受方向键影响
+        if dash.is_dashing() {
+            brick_velocity.linvel = dash.direction * stats.dash_speed;
+            continue;
+        }
+        brick_velocity.linvel = brick_velocity
+            .linvel
+            .lerp(player_input.move_direction * speed.0, grip.0);
+    }
     // // 计算当前速度和目标方向的夹角，如果为-1（夹角为180°）则直接改变速度而不必插值，这样操作会更流畅
     // let cos = brick_velocity.linvel.dot(player_input.move_direction)
     //     / (brick_velocity.linvel.length() * player_input.move_direction.length());
@@ -33,7 +56,4 @@ fn brick_movement(
     //         .linvel
     //         .lerp(player_input.move_direction * BRICK_SPEED, 0.4);
     // }
-    brick_velocity.linvel = brick_velocity
-        .linvel
-        .lerp(player_input.move_direction * brick_speed.0, 0.5);
 }
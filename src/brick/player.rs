@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+
+pub struct PlayerPlugin;
+
+impl Plugin for PlayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlayMode>();
+    }
+}
+
+// 单人或者本地双人，在选择角色界面设置
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PlayMode {
+    #[default]
+    Solo,
+    Coop,
+}
+
+impl PlayMode {
+    pub fn players(self) -> &'static [Player] {
+        match self {
+            PlayMode::Solo => &[Player::One],
+            PlayMode::Coop => &[Player::One, Player::Two],
+        }
+    }
+
+    pub fn toggle(self) -> Self {
+        match self {
+            PlayMode::Solo => PlayMode::Coop,
+            PlayMode::Coop => PlayMode::Solo,
+        }
+    }
+}
+
+// 砖块属于哪个玩家，每个玩家有自己的输入、瞄准、压力和背包
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Player {
+    #[default]
+    One,
+    Two,
+}
+
+impl Player {
+    pub fn index(self) -> usize {
+        match self {
+            Player::One => 0,
+            Player::Two => 1,
+        }
+    }
+
+    // 本地化文本的键
+    pub fn key(self) -> &'static str {
+        match self {
+            Player::One => "player.one",
+            Player::Two => "player.two",
+        }
+    }
+
+    // 双人时用来区分两个玩家的砖块、准星和 HUD
+    pub fn color(self) -> Color {
+        match self {
+            Player::One => Color::srgb(1.0, 0.85, 0.4),
+            Player::Two => Color::srgb(0.4, 0.75, 1.0),
+        }
+    }
+
+    // 进入房间时砖块的位置，双人时左右分开
+    pub fn spawn_position(self, mode: PlayMode) -> Vec3 {
+        let x = match (mode, self) {
+            (PlayMode::Solo, _) => 0.0,
+            (PlayMode::Coop, Player::One) => -150.0,
+            (PlayMode::Coop, Player::Two) => 150.0,
+        };
+        Vec3::new(x, -250.0, 1.0)
+    }
+}
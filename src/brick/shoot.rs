@@ -2,23 +2,34 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
 use crate::{
-    ball::{Ball, OriginalVel},
+    ball::{Ball, DamageCoefficient, OriginalVel},
+    events::{BallShotEvent, Damage},
     ui::AimAngle,
     GameAssets, GameState,
 };
 
 use super::{
+    abilities::{AbilityStats, ChargeShot},
     brick_ball::{spawn_ball, BRICK_BALL_SIZE},
     input::{PlayerInput, ToggleAimEvent},
     inventory::Inventory,
-    stats::BRICK_HEIGHT,
-    Brick,
+    stats::BallDamage,
+    Brick, Dimensions,
 };
 
-const BALL_Y_OFFSET: f32 = BRICK_HEIGHT / 2.0 + BRICK_BALL_SIZE / 2.0 + 9.0;
+// 手里的球放在砖块上方，跟着砖块的高度变化
+fn ball_y_offset(dimensions: &Dimensions) -> f32 {
+    dimensions.height / 2.0 + BRICK_BALL_SIZE / 2.0 + 9.0
+}
+const SHOOT_SPEED: f32 = 500.0;
+
+// 拿在手里还没射出的球，记录拿着它的砖块
+#[derive(Component)]
+pub struct BallInHand(pub Entity);
 
+// 射出这个球的砖块，漏球时由它承受压力
 #[derive(Component)]
-pub struct BallInHand;
+pub struct ShotBy(pub Entity);
 
 pub struct ShootPlugin;
 
@@ -35,23 +46,52 @@ impl Plugin for ShootPlugin {
 
 fn shoot(
     mut commands: Commands,
-    mut player_input: ResMut<PlayerInput>,
-    mut q_ball_in_hand: Query<
-        (&mut Sleeping, &mut Velocity, &mut OriginalVel, Entity),
-        With<BallInHand>,
+    mut q_brick: Query<
+        (
+            &mut PlayerInput,
+            &AimAngle,
+            &mut ChargeShot,
+            &AbilityStats,
+            &BallDamage,
+        ),
+        With<Brick>,
     >,
-    aim_angle: Res<AimAngle>,
+    mut q_ball_in_hand: Query<(
+        &BallInHand,
+        &mut Sleeping,
+        &mut Velocity,
+        &mut OriginalVel,
+        &mut DamageCoefficient,
+        &mut Damage,
+        Entity,
+    )>,
+    mut shot_events: EventWriter<BallShotEvent>,
 ) {
-    if !player_input.shoot {
-        return;
-    }
-    let aim_direction = Vec2::new(aim_angle.0.cos(), aim_angle.0.sin());
-    player_input.toggle_aim = false;
-    for (mut sleep, mut velocity, mut original_vel, entity) in q_ball_in_hand.iter_mut() {
-        commands.entity(entity).remove::<BallInHand>();
+    for (in_hand, mut sleep, mut velocity, mut original_vel, mut coefficient, mut damage, entity) in
+        q_ball_in_hand.iter_mut()
+    {
+        let Ok((mut player_input, aim_angle, mut charge, stats, ball_damage)) =
+            q_brick.get_mut(in_hand.0)
+        else {
+            continue;
+        };
+        if !player_input.shoot {
+            continue;
+        }
+        let aim_direction = Vec2::new(aim_angle.0.cos(), aim_angle.0.sin());
+        player_input.toggle_aim = false;
+        // 蓄力越久出手越快、伤害越高
+        let (speed_multiplier, damage_coefficient) = charge.release(stats);
+        commands
+            .entity(entity)
+            .remove::<BallInHand>()
+            .insert(ShotBy(in_hand.0));
         sleep.sleeping = false;
-        *velocity = Velocity::linear(aim_direction * 500.0);
+        *velocity = Velocity::linear(aim_direction * SHOOT_SPEED * speed_multiplier);
         original_vel.0 = *velocity;
+        coefficient.0 = damage_coefficient;
+        damage.value = ball_damage.0;
+        shot_events.send(BallShotEvent);
     }
 }
 
@@ -59,16 +99,18 @@ fn shoot(
 fn prepare_ball(
     mut commands: Commands,
     assets: Res<GameAssets>,
-    brick_transform: Single<&Transform, With<Brick>>,
+    mut q_brick: Query<(&Transform, &Dimensions, &mut Inventory), With<Brick>>,
     mut events: EventReader<ToggleAimEvent>,
-    q_existion_ball_in_hand: Query<(Entity, &Ball), With<BallInHand>>,
-    mut inventory: ResMut<Inventory>,
+    q_existion_ball_in_hand: Query<(Entity, &Ball, &BallInHand)>,
 ) {
     for event in events.read() {
-        if event.0 {
+        let Ok((brick_transform, dimensions, mut inventory)) = q_brick.get_mut(event.brick) else {
+            continue;
+        };
+        if event.aiming {
             let in_hand_ball_position = Vec2::new(
                 brick_transform.translation.x,
-                brick_transform.translation.y + BALL_Y_OFFSET,
+                brick_transform.translation.y + ball_y_offset(dimensions),
             );
 
             let ball = inventory.pop();
@@ -81,10 +123,13 @@ fn prepare_ball(
                     Vec2::new(0.0, 0.0),
                     true,
                 );
-                commands.entity(ball_entity).insert(BallInHand);
+                commands.entity(ball_entity).insert(BallInHand(event.brick));
             }
         } else {
-            for (entity, ball) in q_existion_ball_in_hand.iter() {
+            for (entity, ball, _) in q_existion_ball_in_hand
+                .iter()
+                .filter(|(_, _, in_hand)| in_hand.0 == event.brick)
+            {
                 commands.entity(entity).despawn_recursive();
                 inventory.push(*ball).expect("can't push ball");
             }
@@ -93,15 +138,17 @@ fn prepare_ball(
 }
 
 fn update_prepare_ball(
-    brick_transform: Single<&Transform, (With<Brick>, Without<BallInHand>)>,
-    mut q_existion_ball_in_hand: Query<&mut Transform, With<BallInHand>>,
+    q_brick: Query<(&Transform, &Dimensions), (With<Brick>, Without<BallInHand>)>,
+    mut q_existion_ball_in_hand: Query<(&mut Transform, &BallInHand)>,
 ) {
-    let in_hand_ball_position = Vec2::new(
-        brick_transform.translation.x,
-        brick_transform.translation.y + BALL_Y_OFFSET,
-    );
-
-    for mut transform in q_existion_ball_in_hand.iter_mut() {
+    for (mut transform, in_hand) in q_existion_ball_in_hand.iter_mut() {
+        let Ok((brick_transform, dimensions)) = q_brick.get(in_hand.0) else {
+            continue;
+        };
+        let in_hand_ball_position = Vec2::new(
+            brick_transform.translation.x,
+            brick_transform.translation.y + ball_y_offset(dimensions),
+        );
         *transform = Transform::from_translation(in_hand_ball_position.extend(1.0));
     }
 }
@@ -3,13 +3,14 @@ use bevy_rapier2d::prelude::*;
 
 use crate::{
     collision_group::*,
-    item::Item,
-    utils::anim_sprite::{AnimSprite, AnimSpriteTimer, AnimationIndices},
-    GameAssets, GameState,
+    utils::anim_sprite::{AnimSprite, AnimSpriteTimer},
+    GameState,
 };
 
 use super::{
-    item_collection::{self, ItemCollection},
+    character::{CharacterRegistry, SelectedCharacters},
+    input::InputDevice,
+    player::{PlayMode, Player},
     stats::*,
     Brick, Dimensions, Speed,
 };
@@ -22,54 +23,96 @@ impl Plugin for SpawnPlugin {
     }
 }
 
-fn spawn_brick(mut commands: Commands, assets: Res<GameAssets>) {
-    let animation_indices = AnimationIndices::new(0, 0);
+// 按每个玩家选中角色的数据生成砖块
+pub fn spawn_brick(
+    mut commands: Commands,
+    characters: Res<CharacterRegistry>,
+    selected: Res<SelectedCharacters>,
+    play_mode: Res<PlayMode>,
+    q_gamepad: Query<Entity, With<Gamepad>>,
+) {
+    // 第一个玩家用键盘，第二个玩家有手柄时用手柄，没有时用另一组键盘按键
+    let mut gamepads = q_gamepad.iter();
+    for &player in play_mode.players() {
+        let device = match player {
+            Player::One => InputDevice::Keyboard,
+            Player::Two => gamepads
+                .next()
+                .map_or(InputDevice::Keyboard, InputDevice::Gamepad),
+        };
+        spawn_player_brick(
+            &mut commands,
+            &characters,
+            &selected,
+            *play_mode,
+            player,
+            device,
+        );
+    }
+}
+
+fn spawn_player_brick(
+    commands: &mut Commands,
+    characters: &CharacterRegistry,
+    selected: &SelectedCharacters,
+    play_mode: PlayMode,
+    player: Player,
+    device: InputDevice,
+) {
+    let character = selected.get(player);
+    let def = characters.get(character);
+    let animation_indices = def.pressure_animation(0.0);
+    let mut sprite = characters.sprite(character, animation_indices.first);
+    if play_mode == PlayMode::Coop {
+        sprite.color = sprite.color.mix(&player.color(), 0.35);
+    }
     let brick_entity = commands
         .spawn((
-            Speed(BRICK_SPEED),
+            Speed(def.speed),
             RigidBody::KinematicVelocityBased,
             LockedAxes::ROTATION_LOCKED,
             Velocity::zero(),
             GravityScale(0.0),
-            Pressure::default(),
-            Sprite::from_atlas_image(
-                assets.brick_texture.clone(),
-                TextureAtlas {
-                    layout: assets.brick_layout.clone(),
-                    index: animation_indices.first,
+            Pressure {
+                current: 0.0,
+                max: def.max_pressure,
+            },
+            (
+                sprite,
+                animation_indices,
+                AnimSpriteTimer::new(0.1),
+                AnimSprite {
+                    repeating: true,
+                    disabled: false,
                 },
             ),
-            animation_indices,
-            AnimSpriteTimer::new(0.1),
-            AnimSprite {
-                repeating: true,
-                disabled: false,
-            },
-            Transform::from_xyz(0.0, -250.0, 1.0),
-            Brick,
+            Transform::from_translation(player.spawn_position(play_mode)),
+            (Brick, player, device, character),
+            def.base_stats(),
+            Grip(def.grip),
             Dimensions {
-                width: BRICK_WIDTH,
-                height: BRICK_HEIGHT,
+                width: def.width,
+                height: def.height,
             },
-            Friction::coefficient(3.0),
-            Restitution::coefficient(3.0),
+            Friction::coefficient(def.friction),
+            Restitution::coefficient(def.restitution),
         ))
         .id();
 
     let collider = commands
         .spawn((
-            Collider::cuboid(BRICK_WIDTH / 2.0, BRICK_HEIGHT / 2.0),
+            Collider::cuboid(def.width / 2.0, def.height / 2.0),
             // ActiveEvents::COLLISION_EVENTS,
             ColliderMassProperties::MassProperties(MassProperties {
-                mass: BRICK_MASS,
+                mass: def.mass,
                 ..default()
             }),
             CollisionGroups::new(GROUP_BRICK, Group::all() ^ GROUP_SENSOR_DEAD_ZONE),
             // SolverGroups::new(GROUP_BRICK, Group::all() ^ GROUP_SENSOR_DEAD_ZONE),
             ActiveCollisionTypes::KINEMATIC_KINEMATIC | ActiveCollisionTypes::KINEMATIC_STATIC,
             // ActiveEvents::COLLISION_EVENTS,
-            Friction::coefficient(3.0),
-            Restitution::coefficient(3.0),
+            Friction::coefficient(def.friction),
+            Restitution::coefficient(def.restitution),
         ))
         .id();
 
@@ -1,24 +1,34 @@
 use bevy::prelude::*;
-use bevy_rapier2d::prelude::Collider;
+use bevy_rapier2d::prelude::Friction;
+use serde::Deserialize;
 
-use crate::GameState;
+use crate::{item::effects::EffectSource, GameState};
 
-use super::{Brick, Dimensions};
+use super::{abilities::AbilityStats, brick_ball::BALL_DAMAGE, Brick, Dimensions, Speed};
 
 // 砖块属性
 pub const BRICK_WIDTH: f32 = 113.0;
 pub const BRICK_HEIGHT: f32 = 38.0;
 pub const BRICK_SPEED: f32 = 500.0;
 pub const BRICK_MASS: f32 = 80.0;
+pub const BRICK_FRICTION: f32 = 3.0;
+pub const BRICK_MAX_PRESSURE: f32 = 100.0;
+// 每帧向目标速度靠拢的比例，越小越容易漂移
+pub const BRICK_GRIP: f32 = 0.5;
 
 pub struct StatsPlugin;
 
 impl Plugin for StatsPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<BrickStats>().add_systems(
-            Update,
-            update_dimensions.run_if(in_state(GameState::Gaming)),
-        );
+        app.init_resource::<BrickStats>()
+            .add_systems(
+                Update,
+                (apply_stat_modifiers, update_dimensions)
+                    .chain()
+                    .run_if(in_state(GameState::Gaming)),
+            )
+            // 放在最后检查，PostUpdate 里对压力的修改（例如无敌模式）先生效
+            .add_systems(Last, check_crushed.run_if(in_state(GameState::Gaming)));
     }
 }
 
@@ -47,22 +57,269 @@ impl Default for Pressure {
     fn default() -> Self {
         Self {
             current: 0.0,
-            max: 100.0,
+            max: BRICK_MAX_PRESSURE,
+        }
+    }
+}
+
+// 可以被道具修改的属性
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stat {
+    Speed,
+    Friction,
+    MaxPressure,
+    Width,
+    Height,
+    BallDamage,
+    DashCooldown,
+    Grip,
+}
+
+impl Stat {
+    pub const ALL: [Stat; 8] = [
+        Stat::Speed,
+        Stat::Friction,
+        Stat::MaxPressure,
+        Stat::Width,
+        Stat::Height,
+        Stat::BallDamage,
+        Stat::DashCooldown,
+        Stat::Grip,
+    ];
+
+    // 属性名称的本地化文本键
+    pub fn key(&self) -> &'static str {
+        match self {
+            Stat::Speed => "stat.speed",
+            Stat::Friction => "stat.friction",
+            Stat::MaxPressure => "stat.max_pressure",
+            Stat::Width => "stat.width",
+            Stat::Height => "stat.height",
+            Stat::BallDamage => "stat.ball_damage",
+            Stat::DashCooldown => "stat.dash_cooldown",
+            Stat::Grip => "stat.grip",
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum ModifierOp {
+    // 加在基础值上
+    Add(f32),
+    // 在加法之后相乘
+    Multiply(f32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatModifier {
+    pub stat: Stat,
+    pub op: ModifierOp,
+    pub source: EffectSource,
+}
+
+// 砖块的基础属性，最终属性 = (基础值 + 所有加法修正) * 所有乘法修正
+#[derive(Component, Debug, Clone)]
+pub struct BaseStats {
+    pub speed: f32,
+    pub friction: f32,
+    pub max_pressure: f32,
+    pub width: f32,
+    pub height: f32,
+    pub ball_damage: f32,
+    pub dash_cooldown: f32,
+    pub grip: f32,
+}
+
+impl Default for BaseStats {
+    fn default() -> Self {
+        Self {
+            speed: BRICK_SPEED,
+            friction: BRICK_FRICTION,
+            max_pressure: BRICK_MAX_PRESSURE,
+            width: BRICK_WIDTH,
+            height: BRICK_HEIGHT,
+            ball_damage: BALL_DAMAGE,
+            dash_cooldown: AbilityStats::default().dash_cooldown,
+            grip: BRICK_GRIP,
         }
     }
 }
 
+impl BaseStats {
+    pub fn get(&self, stat: Stat) -> f32 {
+        match stat {
+            Stat::Speed => self.speed,
+            Stat::Friction => self.friction,
+            Stat::MaxPressure => self.max_pressure,
+            Stat::Width => self.width,
+            Stat::Height => self.height,
+            Stat::BallDamage => self.ball_damage,
+            Stat::DashCooldown => self.dash_cooldown,
+            Stat::Grip => self.grip,
+        }
+    }
+}
+
+#[derive(Component, Debug, Default)]
+pub struct StatModifiers(Vec<StatModifier>);
+
+impl StatModifiers {
+    pub fn add(&mut self, modifier: StatModifier) {
+        self.0.push(modifier);
+    }
+
+    // 移除某个道具或组合带来的所有修正，目前还没有会失效的效果
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn remove_source(&mut self, source: EffectSource) {
+        self.0.retain(|modifier| modifier.source != source);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &StatModifier> {
+        self.0.iter()
+    }
+
+    pub fn value(&self, base: &BaseStats, stat: Stat) -> f32 {
+        let (add, multiply) = self.0.iter().filter(|modifier| modifier.stat == stat).fold(
+            (0.0, 1.0),
+            |(add, multiply), modifier| match modifier.op {
+                ModifierOp::Add(value) => (add + value, multiply),
+                ModifierOp::Multiply(value) => (add, multiply * value),
+            },
+        );
+        (base.get(stat) + add) * multiply
+    }
+}
+
+// 球射出时的伤害，由属性计算
+#[derive(Component)]
+pub struct BallDamage(pub f32);
+
+impl Default for BallDamage {
+    fn default() -> Self {
+        Self(BALL_DAMAGE)
+    }
+}
+
+#[derive(Component)]
+pub struct Grip(pub f32);
+
+impl Default for Grip {
+    fn default() -> Self {
+        Self(BRICK_GRIP)
+    }
+}
+
+// 修正变化时重新计算最终属性
+fn apply_stat_modifiers(
+    mut q_brick: Query<
+        (
+            &BaseStats,
+            &StatModifiers,
+            &mut Speed,
+            &mut Friction,
+            &mut Pressure,
+            &mut Dimensions,
+            &mut BallDamage,
+            &mut AbilityStats,
+            &mut Grip,
+        ),
+        (Changed<StatModifiers>, With<Brick>),
+    >,
+) {
+    for (
+        base,
+        modifiers,
+        mut speed,
+        mut friction,
+        mut pressure,
+        mut dimensions,
+        mut ball_damage,
+        mut abilities,
+        mut grip,
+    ) in q_brick.iter_mut()
+    {
+        speed.0 = modifiers.value(base, Stat::Speed);
+        friction.coefficient = modifiers.value(base, Stat::Friction);
+        pressure.max = modifiers.value(base, Stat::MaxPressure);
+        pressure.current = pressure.current.min(pressure.max);
+        dimensions.width = modifiers.value(base, Stat::Width);
+        dimensions.height = modifiers.value(base, Stat::Height);
+        ball_damage.0 = modifiers.value(base, Stat::BallDamage);
+        abilities.dash_cooldown = modifiers.value(base, Stat::DashCooldown);
+        grip.0 = modifiers.value(base, Stat::Grip).clamp(0.05, 1.0);
+    }
+}
+
+// 碰撞体在子实体上，会跟着父实体的缩放一起变化；缩放相对于角色本身的尺寸
 fn update_dimensions(
     mut q_brick: Query<
-        (&mut Transform, &mut Collider, &Dimensions),
+        (&mut Transform, &Dimensions, &BaseStats),
         (Changed<Dimensions>, With<Brick>),
     >,
 ) {
-    for (mut transform, mut collider, dimensions) in q_brick.iter_mut() {
-        let scale_x = dimensions.width / BRICK_WIDTH;
-        let scale_y = dimensions.height / BRICK_HEIGHT;
+    for (mut transform, dimensions, base) in q_brick.iter_mut() {
+        let scale_x = dimensions.width / base.width;
+        let scale_y = dimensions.height / base.height;
         transform.scale = Vec3::new(scale_x, scale_y, 1.0);
+    }
+}
+
+// 压力满了砖块被压碎，双人时任何一个砖块被压碎本局都结束
+fn check_crushed(
+    q_pressure: Query<&Pressure, With<Brick>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    if q_pressure
+        .iter()
+        .any(|pressure| pressure.current >= pressure.max)
+    {
+        game_state.set(GameState::GameOver);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::Item;
+
+    fn modifier(stat: Stat, op: ModifierOp, item: usize) -> StatModifier {
+        StatModifier {
+            stat,
+            op,
+            source: EffectSource::Item(Item::new(item)),
+        }
+    }
+
+    #[test]
+    fn value_applies_adds_before_multiplies() {
+        let base = BaseStats::default();
+        let mut modifiers = StatModifiers::default();
+        modifiers.add(modifier(Stat::Speed, ModifierOp::Multiply(2.0), 0));
+        modifiers.add(modifier(Stat::Speed, ModifierOp::Add(100.0), 1));
+        assert_eq!(
+            modifiers.value(&base, Stat::Speed),
+            (BRICK_SPEED + 100.0) * 2.0
+        );
+    }
+
+    #[test]
+    fn remove_source_restores_the_base_value() {
+        let base = BaseStats::default();
+        let mut modifiers = StatModifiers::default();
+        modifiers.add(modifier(Stat::Speed, ModifierOp::Add(100.0), 0));
+        modifiers.add(modifier(Stat::Speed, ModifierOp::Multiply(1.5), 0));
+        modifiers.remove_source(EffectSource::Item(Item::new(0)));
+        assert_eq!(modifiers.value(&base, Stat::Speed), BRICK_SPEED);
+        assert_eq!(modifiers.iter().count(), 0);
+    }
 
-        *collider = Collider::cuboid(dimensions.width / 2.0, dimensions.height / 2.0);
+    #[test]
+    fn remove_source_keeps_other_sources() {
+        let base = BaseStats::default();
+        let mut modifiers = StatModifiers::default();
+        modifiers.add(modifier(Stat::Friction, ModifierOp::Add(1.0), 0));
+        modifiers.add(modifier(Stat::Friction, ModifierOp::Add(2.0), 1));
+        modifiers.remove_source(EffectSource::Item(Item::new(0)));
+        assert_eq!(modifiers.value(&base, Stat::Friction), BRICK_FRICTION + 2.0);
     }
 }
@@ -0,0 +1,66 @@
+mod commands;
+mod console;
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    ball::Ball,
+    brick::{inventory::Inventory, stats::Pressure, Brick},
+    GameState,
+};
+
+// 开发者工具，只在启用 dev-tools feature 时编译
+pub struct DevToolsPlugin;
+
+impl Plugin for DevToolsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            RapierDebugRenderPlugin::default(),
+            console::ConsolePlugin,
+            commands::CommandsPlugin,
+        ))
+        .init_resource::<GodMode>()
+        .add_systems(
+            Update,
+            debug_inventory
+                .run_if(in_state(GameState::Gaming))
+                .run_if(not(console::console_open)),
+        )
+        .add_systems(PostUpdate, god_mode.run_if(in_state(GameState::Gaming)));
+    }
+}
+
+// 无敌模式下砖块的压力始终为 0
+#[derive(Resource, Default)]
+pub struct GodMode(pub bool);
+
+fn god_mode(god_mode: Res<GodMode>, mut q_pressure: Query<&mut Pressure, With<Brick>>) {
+    if !god_mode.0 {
+        return;
+    }
+    for mut pressure in q_pressure.iter_mut() {
+        pressure.current = 0.0;
+    }
+}
+
+// 用功能键，字母键已经分给了两个玩家
+fn debug_inventory(
+    mut q_inventory: Query<&mut Inventory, With<Brick>>,
+    key: Res<ButtonInput<KeyCode>>,
+) {
+    for mut inventory in q_inventory.iter_mut() {
+        if key.just_pressed(KeyCode::F5) {
+            let _ = inventory.push(Ball::Tennis);
+        }
+        if key.just_pressed(KeyCode::F6) {
+            inventory.expansion(1);
+        }
+        if key.just_pressed(KeyCode::F7) {
+            inventory.pop();
+        }
+        if key.just_pressed(KeyCode::F8) {
+            inventory.redusing(1);
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use bevy::prelude::*;
+use bevy_rapier2d::render::DebugRenderContext;
+use rand::seq::SliceRandom;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    ball::Ball,
+    brick::{
+        inventory::Inventory,
+        item_collection::{AddItemEvent, ItemCollection},
+        stats::Pressure,
+        Brick,
+    },
+    enemy::{Enemy, EnemySpawner},
+    item::ItemRegistry,
+    world::map::room::{
+        control::ChooseState,
+        loading::LoadingState,
+        select::{SelectedRoom, SelectedRooms},
+        RoomType, Rooms,
+    },
+};
+
+use super::{
+    console::{Console, ConsoleCommandEvent},
+    GodMode,
+};
+
+const HELP: &str = "spawn <enemy> [x y] | give <item> | ball <type> | goto <room_type> \
+| set pressure <N> | godmode | toggle physics-debug";
+
+pub struct CommandsPlugin;
+
+impl Plugin for CommandsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, run_commands);
+    }
+}
+
+enum Command {
+    Spawn { enemy: Enemy, pos: Vec2 },
+    Give(String),
+    Ball(Ball),
+    Goto(RoomType),
+    SetPressure(f32),
+    GodMode,
+    TogglePhysicsDebug,
+    Help,
+}
+
+// 枚举名和 ron 里一样，首字母不区分大小写
+fn parse_enum<T: DeserializeOwned>(word: &str) -> Result<T, String> {
+    let mut chars = word.chars();
+    let name = chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+        .unwrap_or_default();
+    ron::from_str(&name).map_err(|_| format!("unknown name: {}", word))
+}
+
+fn parse_number(word: Option<&str>) -> Result<f32, String> {
+    let word = word.ok_or("missing number")?;
+    word.parse().map_err(|_| format!("not a number: {}", word))
+}
+
+fn parse(line: &str) -> Result<Command, String> {
+    let mut words = line.split_whitespace();
+    let name = words.next().unwrap_or_default();
+    let arg = words.next();
+    match (name, arg) {
+        ("spawn", Some(enemy)) => {
+            let enemy = parse_enum(enemy)?;
+            let pos = match words.next() {
+                Some(x) => Vec2::new(parse_number(Some(x))?, parse_number(words.next())?),
+                None => Vec2::ZERO,
+            };
+            Ok(Command::Spawn { enemy, pos })
+        }
+        ("give", Some(item)) => Ok(Command::Give(item.to_string())),
+        ("ball", Some(ball)) => Ok(Command::Ball(parse_enum(ball)?)),
+        ("goto", Some(room_type)) => Ok(Command::Goto(parse_enum(room_type)?)),
+        ("set", Some("pressure")) => Ok(Command::SetPressure(parse_number(words.next())?)),
+        ("godmode", None) => Ok(Command::GodMode),
+        ("toggle", Some("physics-debug")) => Ok(Command::TogglePhysicsDebug),
+        ("help", None) => Ok(Command::Help),
+        _ => Err(format!("unknown command: {}", line)),
+    }
+}
+
+fn run_commands(
+    mut command_events: EventReader<ConsoleCommandEvent>,
+    mut console: ResMut<Console>,
+    mut enemy_spawner: EnemySpawner,
+    registry: Res<ItemRegistry>,
+    mut collection: ResMut<ItemCollection>,
+    mut add_item_events: EventWriter<AddItemEvent>,
+    mut q_inventory: Query<&mut Inventory, With<Brick>>,
+    rooms: Option<Res<Rooms>>,
+    mut selected_rooms: ResMut<SelectedRooms>,
+    loading_state: Res<State<LoadingState>>,
+    mut next_choose_state: ResMut<NextState<ChooseState>>,
+    mut next_loading_state: ResMut<NextState<LoadingState>>,
+    mut q_pressure: Query<&mut Pressure, With<Brick>>,
+    mut god_mode: ResMut<GodMode>,
+    mut debug_render: ResMut<DebugRenderContext>,
+) {
+    for ConsoleCommandEvent(line) in command_events.read() {
+        let command = match parse(line) {
+            Ok(command) => command,
+            Err(error) => {
+                console.print(error);
+                continue;
+            }
+        };
+        let reply = match command {
+            Command::Spawn { enemy, pos } => {
+                enemy_spawner.spawn(enemy, pos);
+                format!("spawned {:?} at {}", enemy, pos)
+            }
+            Command::Give(id) => match registry.find(&id) {
+                Some(item) => {
+                    collection.add(item);
+                    add_item_events.send(AddItemEvent(item));
+                    format!("gave {}", id)
+                }
+                None => {
+                    let ids = registry
+                        .iter()
+                        .map(|(_, def)| def.id.as_str())
+                        .collect::<Vec<_>>();
+                    format!("unknown item {}, available: {}", id, ids.join(" "))
+                }
+            },
+            // 每个砖块各给一个
+            Command::Ball(ball) => {
+                let added = q_inventory
+                    .iter_mut()
+                    .map(|mut inventory| inventory.push(ball))
+                    .filter(Result::is_ok)
+                    .count();
+                if added > 0 {
+                    format!("added {:?} to {} inventories", ball, added)
+                } else {
+                    "inventory is full".to_string()
+                }
+            }
+            Command::Goto(room_type) => goto(
+                room_type,
+                rooms.as_deref(),
+                &mut selected_rooms,
+                loading_state.get(),
+                &mut next_choose_state,
+                &mut next_loading_state,
+            ),
+            Command::SetPressure(value) => {
+                for mut pressure in q_pressure.iter_mut() {
+                    pressure.current = value.clamp(0.0, pressure.max);
+                }
+                format!("pressure set to {}", value)
+            }
+            Command::GodMode => {
+                god_mode.0 = !god_mode.0;
+                format!("godmode {}", if god_mode.0 { "on" } else { "off" })
+            }
+            Command::TogglePhysicsDebug => {
+                debug_render.enabled = !debug_render.enabled;
+                format!(
+                    "physics debug {}",
+                    if debug_render.enabled { "on" } else { "off" }
+                )
+            }
+            Command::Help => HELP.to_string(),
+        };
+        console.print(reply);
+    }
+}
+
+// 和走进门一样：把目标房间放进候选房间，然后开始淡出
+fn goto(
+    room_type: RoomType,
+    rooms: Option<&Rooms>,
+    selected_rooms: &mut SelectedRooms,
+    loading_state: &LoadingState,
+    next_choose_state: &mut NextState<ChooseState>,
+    next_loading_state: &mut NextState<LoadingState>,
+) -> String {
+    if *loading_state != LoadingState::Ready {
+        return "already changing rooms".to_string();
+    }
+    let Some(room) =
+        rooms.and_then(|rooms| rooms.rooms.iter().find(|room| room.room_type == room_type))
+    else {
+        return format!("no room of type {:?}", room_type);
+    };
+    let encounter = room
+        .encounters
+        .as_ref()
+        .and_then(|encounters| encounters.choose(&mut rand::thread_rng()).cloned());
+    selected_rooms.rooms.push(SelectedRoom {
+        room_type: room.room_type,
+        num_exits: room.num_exits,
+        arena: room.arena,
+        encounter,
+    });
+    selected_rooms.index = selected_rooms.rooms.len() - 1;
+    next_choose_state.set(ChooseState::Ready);
+    next_loading_state.set(LoadingState::FadeOut);
+    format!("going to {:?}", room_type)
+}
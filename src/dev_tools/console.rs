@@ -0,0 +1,163 @@
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        InputSystem,
+    },
+    prelude::*,
+};
+
+use crate::{GameAssets, GameState};
+
+const MAX_LOG_LINES: usize = 12;
+
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ConsoleCommandEvent>()
+            .init_resource::<Console>()
+            .add_systems(OnExit(GameState::AssetLoading), spawn_console)
+            .add_systems(
+                PreUpdate,
+                (toggle_console, console_input).chain().after(InputSystem),
+            )
+            .add_systems(Update, update_console_ui);
+    }
+}
+
+// 在控制台里输入的一行命令
+#[derive(Event)]
+pub struct ConsoleCommandEvent(pub String);
+
+#[derive(Resource, Default)]
+pub struct Console {
+    open: bool,
+    input: String,
+    log: Vec<String>,
+    // 按上方向键取回之前输入的命令
+    history: Vec<String>,
+    history_index: Option<usize>,
+}
+
+impl Console {
+    pub fn print(&mut self, line: impl Into<String>) {
+        self.log.push(line.into());
+        if self.log.len() > MAX_LOG_LINES {
+            self.log.remove(0);
+        }
+    }
+}
+
+pub fn console_open(console: Res<Console>) -> bool {
+    console.open
+}
+
+#[derive(Component)]
+struct ConsoleRoot;
+
+#[derive(Component)]
+struct ConsoleText;
+
+fn spawn_console(mut commands: Commands, assets: Res<GameAssets>) {
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                top: Val::Px(0.0),
+                position_type: PositionType::Absolute,
+                padding: UiRect::all(Val::Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+            // 显示在所有界面的最上层
+            GlobalZIndex(100),
+            Visibility::Hidden,
+            ConsoleRoot,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font: assets.font.clone(),
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.8, 1.0, 0.8)),
+                ConsoleText,
+            ));
+        });
+}
+
+// ` 键打开或关闭控制台
+fn toggle_console(mut console: ResMut<Console>, keys: Res<ButtonInput<KeyCode>>) {
+    if keys.just_pressed(KeyCode::Backquote) {
+        console.open = !console.open;
+        console.input.clear();
+    }
+}
+
+// 控制台打开时吃掉所有键盘输入，避免砖块跟着移动
+fn console_input(
+    mut console: ResMut<Console>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut command_events: EventWriter<ConsoleCommandEvent>,
+) {
+    if !console.open {
+        keyboard_events.clear();
+        return;
+    }
+    for event in keyboard_events.read() {
+        if !event.state.is_pressed() || event.key_code == KeyCode::Backquote {
+            continue;
+        }
+        match &event.logical_key {
+            Key::Character(text) => console.input.push_str(text),
+            Key::Space => console.input.push(' '),
+            Key::Backspace => {
+                console.input.pop();
+            }
+            Key::ArrowUp => {
+                let index = match console.history_index {
+                    Some(index) => index.saturating_sub(1),
+                    None => console.history.len().saturating_sub(1),
+                };
+                if let Some(line) = console.history.get(index).cloned() {
+                    console.input = line;
+                    console.history_index = Some(index);
+                }
+            }
+            Key::Enter => {
+                let line = std::mem::take(&mut console.input);
+                let line = line.trim();
+                console.history_index = None;
+                if line.is_empty() {
+                    continue;
+                }
+                console.print(format!("> {}", line));
+                console.history.push(line.to_string());
+                command_events.send(ConsoleCommandEvent(line.to_string()));
+            }
+            _ => {}
+        }
+    }
+    keys.reset_all();
+}
+
+fn update_console_ui(
+    console: Res<Console>,
+    mut root_visibility: Single<&mut Visibility, With<ConsoleRoot>>,
+    mut text: Single<&mut Text, With<ConsoleText>>,
+) {
+    if !console.is_changed() {
+        return;
+    }
+    **root_visibility = if console.open {
+        Visibility::Visible
+    } else {
+        Visibility::Hidden
+    };
+    let mut content = console.log.join("\n");
+    content.push_str(&format!("\n> {}_", console.input));
+    text.0 = content;
+}
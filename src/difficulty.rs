@@ -0,0 +1,279 @@
+use std::fs::File;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use ron::de::from_reader;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    brick::stats::{BrickStats, Pressure},
+    enemy::Enemy,
+    GameState,
+};
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DifficultyConfig>()
+            .add_systems(Startup, load_difficulty)
+            .add_systems(
+                Update,
+                scale_enemy_pressure.run_if(in_state(GameState::Gaming)),
+            );
+    }
+}
+
+// 难度选择，由设置文件读取和保存
+#[derive(
+    Resource, Component, Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, Hash,
+)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    // 使用 CustomDifficulty 里的修正
+    Custom,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Custom,
+    ];
+
+    // 本地化文本的键
+    pub fn key(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "difficulty.easy",
+            Difficulty::Normal => "difficulty.normal",
+            Difficulty::Hard => "difficulty.hard",
+            Difficulty::Custom => "difficulty.custom",
+        }
+    }
+}
+
+// 各项数值的倍率，1.0 表示不变
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(default)]
+pub struct DifficultyModifiers {
+    // 敌人的最大压力
+    pub enemy_pressure: f32,
+    // 敌人碰到砖块造成的压力
+    pub contact_damage: f32,
+    // 漏球造成的压力
+    pub ball_lost_damage: f32,
+    // 每个房间的敌人数量
+    pub enemy_count: f32,
+    // 稀有和传说道具的权重
+    pub rare_items: f32,
+}
+
+impl Default for DifficultyModifiers {
+    fn default() -> Self {
+        Self {
+            enemy_pressure: 1.0,
+            contact_damage: 1.0,
+            ball_lost_damage: 1.0,
+            enemy_count: 1.0,
+            rare_items: 1.0,
+        }
+    }
+}
+
+impl DifficultyModifiers {
+    // 可以在菜单里调整的修正，和它们的本地化文本键
+    pub const FIELDS: [(Modifier, &'static str); 5] = [
+        (Modifier::EnemyPressure, "difficulty.enemy_pressure"),
+        (Modifier::ContactDamage, "difficulty.contact_damage"),
+        (Modifier::BallLostDamage, "difficulty.ball_lost_damage"),
+        (Modifier::EnemyCount, "difficulty.enemy_count"),
+        (Modifier::RareItems, "difficulty.rare_items"),
+    ];
+
+    pub fn get(&self, modifier: Modifier) -> f32 {
+        match modifier {
+            Modifier::EnemyPressure => self.enemy_pressure,
+            Modifier::ContactDamage => self.contact_damage,
+            Modifier::BallLostDamage => self.ball_lost_damage,
+            Modifier::EnemyCount => self.enemy_count,
+            Modifier::RareItems => self.rare_items,
+        }
+    }
+
+    pub fn get_mut(&mut self, modifier: Modifier) -> &mut f32 {
+        match modifier {
+            Modifier::EnemyPressure => &mut self.enemy_pressure,
+            Modifier::ContactDamage => &mut self.contact_damage,
+            Modifier::BallLostDamage => &mut self.ball_lost_damage,
+            Modifier::EnemyCount => &mut self.enemy_count,
+            Modifier::RareItems => &mut self.rare_items,
+        }
+    }
+
+    // 每深入一个房间，倍率按 per_room 线性增加，没有写的修正不随深度变化
+    fn at_depth(&self, per_room: &HashMap<Modifier, f32>, depth: usize) -> Self {
+        let mut scaled = *self;
+        for (modifier, _) in Self::FIELDS {
+            let increase = per_room.get(&modifier).copied().unwrap_or(0.0);
+            *scaled.get_mut(modifier) *= 1.0 + increase * depth as f32;
+        }
+        scaled
+    }
+
+    // 房间里实际生成的敌人数量，至少一个
+    fn scaled_enemy_count(&self, base: usize) -> usize {
+        if base == 0 {
+            return 0;
+        }
+        ((base as f32 * self.enemy_count).round() as usize).max(1)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Modifier {
+    EnemyPressure,
+    ContactDamage,
+    BallLostDamage,
+    EnemyCount,
+    RareItems,
+}
+
+// 自定义难度的修正，由设置文件读取和保存
+#[derive(Resource, Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+#[serde(transparent)]
+pub struct CustomDifficulty(pub DifficultyModifiers);
+
+// 各难度的修正和随深度的变化，启动时从 difficulty.ron 读取
+#[derive(Resource, Deserialize, Debug, Default)]
+pub struct DifficultyConfig {
+    pub presets: HashMap<Difficulty, DifficultyModifiers>,
+    // 每深入一个房间各项倍率增加的比例
+    #[serde(default)]
+    pub per_room: HashMap<Modifier, f32>,
+}
+
+impl DifficultyConfig {
+    // 选中难度的修正，还没有考虑深度
+    pub fn modifiers(
+        &self,
+        difficulty: Difficulty,
+        custom: &CustomDifficulty,
+    ) -> DifficultyModifiers {
+        match difficulty {
+            Difficulty::Custom => custom.0,
+            _ => self.presets.get(&difficulty).copied().unwrap_or_default(),
+        }
+    }
+}
+
+// 当前难度在当前深度下的倍率，伤害和压力在生成或结算的地方统一乘上
+#[derive(SystemParam)]
+pub struct DifficultyScaling<'w> {
+    difficulty: Res<'w, Difficulty>,
+    custom: Res<'w, CustomDifficulty>,
+    config: Res<'w, DifficultyConfig>,
+    brick_stats: Res<'w, BrickStats>,
+}
+
+impl DifficultyScaling<'_> {
+    pub fn modifiers(&self) -> DifficultyModifiers {
+        self.config
+            .modifiers(*self.difficulty, &self.custom)
+            .at_depth(&self.config.per_room, self.brick_stats.current_room)
+    }
+
+    pub fn enemy_count(&self, base: usize) -> usize {
+        self.modifiers().scaled_enemy_count(base)
+    }
+}
+
+fn load_difficulty(mut commands: Commands) {
+    let file =
+        File::open("assets/difficulty/difficulty.ron").expect("Failed to open difficulty.ron");
+    let config: DifficultyConfig = from_reader(file).expect("Unable to load difficulty.ron");
+    commands.insert_resource(config);
+}
+
+// 敌人生成时按难度和深度调整最大压力，各种敌人不用分别处理
+fn scale_enemy_pressure(
+    mut q_enemy: Query<&mut Pressure, Added<Enemy>>,
+    scaling: DifficultyScaling,
+) {
+    if q_enemy.is_empty() {
+        return;
+    }
+    let multiplier = scaling.modifiers().enemy_pressure;
+    for mut pressure in q_enemy.iter_mut() {
+        pressure.max *= multiplier;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EPSILON: f32 = 1e-4;
+
+    fn modifiers(value: f32) -> DifficultyModifiers {
+        DifficultyModifiers {
+            enemy_pressure: value,
+            contact_damage: value,
+            ball_lost_damage: value,
+            enemy_count: value,
+            rare_items: value,
+        }
+    }
+
+    #[test]
+    fn at_depth_zero_is_unchanged() {
+        let per_room = HashMap::from([(Modifier::EnemyPressure, 0.5)]);
+        assert_eq!(modifiers(1.5).at_depth(&per_room, 0), modifiers(1.5));
+    }
+
+    #[test]
+    fn at_depth_scales_linearly_per_room() {
+        let per_room = HashMap::from([(Modifier::EnemyPressure, 0.1)]);
+        let scaled = modifiers(2.0).at_depth(&per_room, 5);
+        assert!((scaled.enemy_pressure - 3.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn at_depth_leaves_missing_modifiers_alone() {
+        let per_room = HashMap::from([(Modifier::EnemyPressure, 0.1)]);
+        let scaled = modifiers(1.0).at_depth(&per_room, 10);
+        assert_eq!(scaled.contact_damage, 1.0);
+        assert_eq!(scaled.ball_lost_damage, 1.0);
+        assert_eq!(scaled.enemy_count, 1.0);
+        assert_eq!(scaled.rare_items, 1.0);
+    }
+
+    #[test]
+    fn missing_per_room_does_not_scale() {
+        let config: DifficultyConfig = ron::from_str("(presets: {})").unwrap();
+        assert_eq!(
+            modifiers(1.0).at_depth(&config.per_room, 10),
+            modifiers(1.0)
+        );
+    }
+
+    #[test]
+    fn enemy_count_rounds_the_scaled_count() {
+        assert_eq!(modifiers(1.25).scaled_enemy_count(4), 5);
+        assert_eq!(modifiers(0.75).scaled_enemy_count(3), 2);
+    }
+
+    #[test]
+    fn enemy_count_keeps_at_least_one_enemy() {
+        assert_eq!(modifiers(0.1).scaled_enemy_count(2), 1);
+    }
+
+    #[test]
+    fn enemy_count_of_an_empty_room_stays_empty() {
+        assert_eq!(modifiers(2.0).scaled_enemy_count(0), 0);
+    }
+}
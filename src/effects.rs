@@ -0,0 +1,62 @@
+mod hit_stop;
+mod particles;
+mod shake;
+mod trail;
+
+use bevy::prelude::*;
+
+use crate::menu::DisplayQuality;
+
+pub use particles::{BurstKind, ParticleBurstEvent};
+
+pub struct EffectsPlugin;
+
+impl Plugin for EffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            particles::ParticlesPlugin,
+            shake::ShakePlugin,
+            hit_stop::HitStopPlugin,
+            trail::TrailPlugin,
+        ));
+    }
+}
+
+// 不同画质下开启的特效
+#[derive(Debug, Clone, Copy)]
+pub struct EffectToggles {
+    pub particles: bool,
+    // 粒子数量的倍率
+    pub particle_density: f32,
+    pub trails: bool,
+    pub screen_shake: bool,
+    pub hit_stop: bool,
+}
+
+impl DisplayQuality {
+    pub fn effects(&self) -> EffectToggles {
+        match self {
+            DisplayQuality::Low => EffectToggles {
+                particles: false,
+                particle_density: 0.0,
+                trails: false,
+                screen_shake: false,
+                hit_stop: false,
+            },
+            DisplayQuality::Medium => EffectToggles {
+                particles: true,
+                particle_density: 0.5,
+                trails: false,
+                screen_shake: true,
+                hit_stop: true,
+            },
+            DisplayQuality::High => EffectToggles {
+                particles: true,
+                particle_density: 1.0,
+                trails: true,
+                screen_shake: true,
+                hit_stop: true,
+            },
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+
+use crate::{brick::stats::Pressure, events::DamageEvent, menu::DisplayQuality, GameState};
+
+pub struct HitStopPlugin;
+
+impl Plugin for HitStopPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HitStop>()
+            .add_systems(
+                Update,
+                (start_hit_stop, end_hit_stop)
+                    .chain()
+                    .run_if(in_state(GameState::Gaming)),
+            )
+            .add_systems(OnExit(GameState::Gaming), cancel_hit_stop);
+    }
+}
+
+// 伤害不低于受击者最大压力的这个比例才算重击，普通的击球和敌人的接触都达不到
+const HEAVY_HIT_RATIO: f32 = 0.3;
+// 顿帧持续的真实时间
+const HIT_STOP_SECONDS: f32 = 0.06;
+// 顿帧期间的游戏速度
+const HIT_STOP_SPEED: f32 = 0.05;
+
+#[derive(Resource, Default)]
+struct HitStop(Option<Timer>);
+
+fn start_hit_stop(
+    mut damage_events: EventReader<DamageEvent>,
+    q_pressure: Query<&Pressure>,
+    mut hit_stop: ResMut<HitStop>,
+    mut time: ResMut<Time<Virtual>>,
+    quality: Res<DisplayQuality>,
+) {
+    let heavy_hit = damage_events.read().any(|event| {
+        q_pressure
+            .get(event.victim)
+            .is_ok_and(|pressure| event.damage.value >= pressure.max * HEAVY_HIT_RATIO)
+    });
+    if !heavy_hit || !quality.effects().hit_stop {
+        return;
+    }
+    time.set_relative_speed(HIT_STOP_SPEED);
+    hit_stop.0 = Some(Timer::from_seconds(HIT_STOP_SECONDS, TimerMode::Once));
+}
+
+// 虚拟时间被放慢了，计时要用真实时间
+fn end_hit_stop(
+    mut hit_stop: ResMut<HitStop>,
+    mut time: ResMut<Time<Virtual>>,
+    real_time: Res<Time<Real>>,
+) {
+    let Some(timer) = hit_stop.0.as_mut() else {
+        return;
+    };
+    timer.tick(real_time.delta());
+    if timer.finished() {
+        time.set_relative_speed(1.0);
+        hit_stop.0 = None;
+    }
+}
+
+fn cancel_hit_stop(mut hit_stop: ResMut<HitStop>, mut time: ResMut<Time<Virtual>>) {
+    time.set_relative_speed(1.0);
+    hit_stop.0 = None;
+}
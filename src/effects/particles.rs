@@ -0,0 +1,158 @@
+use bevy::prelude::*;
+use rand::{thread_rng, Rng};
+
+use crate::{
+    events::EnemyDeathEvent, menu::DisplayQuality, world::map::room::loading::RoomComponents,
+    GameState,
+};
+
+pub struct ParticlesPlugin;
+
+impl Plugin for ParticlesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ParticleBurstEvent>().add_systems(
+            Update,
+            (burst_on_enemy_death, spawn_bursts, update_particles)
+                .chain()
+                .run_if(in_state(GameState::Gaming)),
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BurstKind {
+    // 球撞到东西
+    Impact,
+    // 敌人被消灭
+    Death,
+    // 碎裂的砖屑
+    Debris,
+    // 完美击球
+    Perfect,
+}
+
+struct BurstStyle {
+    count: usize,
+    speed: f32,
+    size: f32,
+    lifetime: f32,
+    gravity: f32,
+    color: Color,
+}
+
+impl BurstKind {
+    fn style(&self) -> BurstStyle {
+        match self {
+            BurstKind::Impact => BurstStyle {
+                count: 6,
+                speed: 150.0,
+                size: 4.0,
+                lifetime: 0.25,
+                gravity: 0.0,
+                color: Color::srgb(1.0, 0.95, 0.7),
+            },
+            BurstKind::Death => BurstStyle {
+                count: 24,
+                speed: 300.0,
+                size: 6.0,
+                lifetime: 0.5,
+                gravity: 0.0,
+                color: Color::srgb(1.0, 0.35, 0.25),
+            },
+            BurstKind::Debris => BurstStyle {
+                count: 12,
+                speed: 200.0,
+                size: 8.0,
+                lifetime: 0.8,
+                gravity: -600.0,
+                color: Color::srgb(0.55, 0.3, 0.2),
+            },
+            BurstKind::Perfect => BurstStyle {
+                count: 16,
+                speed: 260.0,
+                size: 5.0,
+                lifetime: 0.4,
+                gravity: 0.0,
+                color: Color::srgb(1.0, 0.8, 0.2),
+            },
+        }
+    }
+}
+
+#[derive(Event)]
+pub struct ParticleBurstEvent {
+    pub pos: Vec2,
+    pub kind: BurstKind,
+}
+
+#[derive(Component)]
+struct Particle {
+    velocity: Vec2,
+    gravity: f32,
+    lifetime: Timer,
+}
+
+fn burst_on_enemy_death(
+    mut death_events: EventReader<EnemyDeathEvent>,
+    mut burst_events: EventWriter<ParticleBurstEvent>,
+) {
+    // 敌人碎裂时除了爆开的粒子还会撒出碎屑
+    for event in death_events.read() {
+        for kind in [BurstKind::Death, BurstKind::Debris] {
+            burst_events.send(ParticleBurstEvent {
+                pos: event.pos,
+                kind,
+            });
+        }
+    }
+}
+
+fn spawn_bursts(
+    mut commands: Commands,
+    mut burst_events: EventReader<ParticleBurstEvent>,
+    quality: Res<DisplayQuality>,
+) {
+    let effects = quality.effects();
+    if !effects.particles {
+        burst_events.clear();
+        return;
+    }
+    let mut rng = thread_rng();
+    for event in burst_events.read() {
+        let style = event.kind.style();
+        let count = (style.count as f32 * effects.particle_density).ceil() as usize;
+        for _ in 0..count {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            let speed = style.speed * rng.gen_range(0.4..1.0);
+            commands.spawn((
+                Sprite::from_color(style.color, Vec2::splat(style.size)),
+                Transform::from_translation(event.pos.extend(3.0)),
+                Particle {
+                    velocity: Vec2::from_angle(angle) * speed,
+                    gravity: style.gravity,
+                    lifetime: Timer::from_seconds(style.lifetime, TimerMode::Once),
+                },
+                RoomComponents,
+            ));
+        }
+    }
+}
+
+fn update_particles(
+    mut commands: Commands,
+    mut q_particle: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    for (entity, mut particle, mut transform, mut sprite) in q_particle.iter_mut() {
+        particle.lifetime.tick(time.delta());
+        if particle.lifetime.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        particle.velocity.y += particle.gravity * time.delta_secs();
+        transform.translation += (particle.velocity * time.delta_secs()).extend(0.0);
+        let remaining = 1.0 - particle.lifetime.fraction();
+        sprite.color.set_alpha(remaining);
+        transform.scale = Vec3::splat(0.5 + remaining * 0.5);
+    }
+}
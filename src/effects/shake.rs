@@ -0,0 +1,73 @@
+use bevy::prelude::*;
+use rand::{thread_rng, Rng};
+
+use crate::{events::DamageEvent, menu::DisplayQuality, world::camera::MainCamera, GameState};
+
+pub struct ShakePlugin;
+
+impl Plugin for ShakePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraShake>()
+            .add_systems(
+                Update,
+                (add_trauma, shake_camera)
+                    .chain()
+                    .run_if(in_state(GameState::Gaming)),
+            )
+            .add_systems(OnExit(GameState::Gaming), reset_shake);
+    }
+}
+
+// 每点伤害增加的震动强度
+const TRAUMA_PER_DAMAGE: f32 = 0.02;
+// 震动强度为 1 时镜头的最大偏移
+const MAX_SHAKE_OFFSET: f32 = 12.0;
+// 每秒衰减的震动强度
+const TRAUMA_DECAY: f32 = 1.5;
+
+// 震动强度 trauma 在 0~1 之间，偏移量和它的平方成正比，小伤害几乎察觉不到
+#[derive(Resource, Default)]
+struct CameraShake {
+    trauma: f32,
+    offset: Vec2,
+}
+
+fn add_trauma(
+    mut damage_events: EventReader<DamageEvent>,
+    mut shake: ResMut<CameraShake>,
+    quality: Res<DisplayQuality>,
+) {
+    if !quality.effects().screen_shake {
+        damage_events.clear();
+        return;
+    }
+    for event in damage_events.read() {
+        shake.trauma = (shake.trauma + event.damage.value * TRAUMA_PER_DAMAGE).min(1.0);
+    }
+}
+
+// 使用真实时间，顿帧期间镜头依然在震动
+fn shake_camera(
+    mut shake: ResMut<CameraShake>,
+    mut camera_transform: Single<&mut Transform, With<MainCamera>>,
+    time: Res<Time<Real>>,
+) {
+    if shake.trauma <= 0.0 && shake.offset == Vec2::ZERO {
+        return;
+    }
+    let mut rng = thread_rng();
+    shake.trauma = (shake.trauma - TRAUMA_DECAY * time.delta_secs()).max(0.0);
+    let strength = shake.trauma * shake.trauma * MAX_SHAKE_OFFSET;
+    let offset = Vec2::new(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)) * strength;
+
+    camera_transform.translation += (offset - shake.offset).extend(0.0);
+    shake.offset = offset;
+}
+
+fn reset_shake(
+    mut shake: ResMut<CameraShake>,
+    mut camera_transform: Single<&mut Transform, With<MainCamera>>,
+) {
+    camera_transform.translation -= shake.offset.extend(0.0);
+    *shake = CameraShake::default();
+}
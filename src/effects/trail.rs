@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    ball::Ball, brick::BallInHand, menu::DisplayQuality, world::map::room::loading::RoomComponents,
+    GameState,
+};
+
+pub struct TrailPlugin;
+
+impl Plugin for TrailPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(TrailTimer(Timer::from_seconds(
+            TRAIL_INTERVAL,
+            TimerMode::Repeating,
+        )))
+        .add_systems(
+            Update,
+            (spawn_trails, fade_trails).run_if(in_state(GameState::Gaming)),
+        );
+    }
+}
+
+// 每隔多久留下一个残影
+const TRAIL_INTERVAL: f32 = 0.03;
+const TRAIL_LIFETIME: f32 = 0.2;
+const TRAIL_ALPHA: f32 = 0.35;
+// 速度太慢的球不留残影
+const MIN_TRAIL_SPEED: f32 = 100.0;
+
+#[derive(Resource)]
+struct TrailTimer(Timer);
+
+#[derive(Component)]
+struct TrailGhost(Timer);
+
+fn spawn_trails(
+    mut commands: Commands,
+    mut timer: ResMut<TrailTimer>,
+    q_ball: Query<(&Sprite, &GlobalTransform, &Velocity), (With<Ball>, Without<BallInHand>)>,
+    quality: Res<DisplayQuality>,
+    time: Res<Time>,
+) {
+    if !quality.effects().trails || !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    for (sprite, transform, velocity) in q_ball.iter() {
+        if velocity.linvel.length() < MIN_TRAIL_SPEED {
+            continue;
+        }
+        let mut ghost = sprite.clone();
+        ghost.color.set_alpha(TRAIL_ALPHA);
+        let mut ghost_transform = transform.compute_transform();
+        // 残影画在球的下面
+        ghost_transform.translation.z -= 0.1;
+        commands.spawn((
+            ghost,
+            ghost_transform,
+            TrailGhost(Timer::from_seconds(TRAIL_LIFETIME, TimerMode::Once)),
+            RoomComponents,
+        ));
+    }
+}
+
+fn fade_trails(
+    mut commands: Commands,
+    mut q_ghost: Query<(Entity, &mut TrailGhost, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    for (entity, mut ghost, mut sprite) in q_ghost.iter_mut() {
+        ghost.0.tick(time.delta());
+        if ghost.0.finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        sprite
+            .color
+            .set_alpha(TRAIL_ALPHA * (1.0 - ghost.0.fraction()));
+    }
+}
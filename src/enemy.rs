@@ -6,8 +6,20 @@ pub mod pride;
 pub mod sloth;
 pub mod wrath;
 
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use envy::SpawnEnvyEvent;
+use gluttony::SpawnGluttonyEvent;
+use greed::SpawnGreedEvent;
+use lust::SpawnLustEvent;
+use pride::SpawnPrideEvent;
+use sloth::SpawnSlothEvent;
+use wrath::SpawnWrathEvent;
+
+// 敌人碰到砖块时造成的压力，难度倍率在结算伤害时统一乘上
+pub const CONTACT_DAMAGE: f32 = 20.0;
 
 pub struct EnemyPlugin;
 
@@ -25,7 +37,7 @@ impl Plugin for EnemyPlugin {
     }
 }
 
-#[derive(Component, Deserialize, Debug, Clone, Copy)]
+#[derive(Component, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Enemy {
     Sloth,
     BossA,
@@ -36,3 +48,60 @@ pub enum Enemy {
     Wrath,
     Lust,
 }
+
+impl Enemy {
+    // 敌人名称的本地化文本键
+    pub fn key(&self) -> &'static str {
+        match self {
+            Enemy::Sloth => "enemy.sloth",
+            Enemy::BossA => "enemy.boss_a",
+            Enemy::Envy => "enemy.envy",
+            Enemy::Gluttony => "enemy.gluttony",
+            Enemy::Greed => "enemy.greed",
+            Enemy::Pride => "enemy.pride",
+            Enemy::Wrath => "enemy.wrath",
+            Enemy::Lust => "enemy.lust",
+        }
+    }
+}
+
+// 按敌人类型发送对应的生成事件
+#[derive(SystemParam)]
+pub struct EnemySpawner<'w> {
+    sloth: EventWriter<'w, SpawnSlothEvent>,
+    envy: EventWriter<'w, SpawnEnvyEvent>,
+    gluttony: EventWriter<'w, SpawnGluttonyEvent>,
+    greed: EventWriter<'w, SpawnGreedEvent>,
+    pride: EventWriter<'w, SpawnPrideEvent>,
+    wrath: EventWriter<'w, SpawnWrathEvent>,
+    lust: EventWriter<'w, SpawnLustEvent>,
+}
+
+impl EnemySpawner<'_> {
+    pub fn spawn(&mut self, enemy: Enemy, pos: Vec2) {
+        match enemy {
+            // BossA 暂时还没有自己的实现
+            Enemy::Sloth | Enemy::BossA => {
+                self.sloth.send(SpawnSlothEvent { pos });
+            }
+            Enemy::Envy => {
+                self.envy.send(SpawnEnvyEvent { pos });
+            }
+            Enemy::Gluttony => {
+                self.gluttony.send(SpawnGluttonyEvent { pos });
+            }
+            Enemy::Greed => {
+                self.greed.send(SpawnGreedEvent { pos });
+            }
+            Enemy::Pride => {
+                self.pride.send(SpawnPrideEvent { pos });
+            }
+            Enemy::Wrath => {
+                self.wrath.send(SpawnWrathEvent { pos });
+            }
+            Enemy::Lust => {
+                self.lust.send(SpawnLustEvent { pos });
+            }
+        }
+    }
+}
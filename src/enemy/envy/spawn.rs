@@ -2,9 +2,11 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
 use crate::{
+    audio::PlaySound,
     brick::stats::{Pressure, BRICK_MASS},
     collision_group::{GROUP_ENEMY, GROUP_SENSOR_DEAD_ZONE},
     enemy::{envy::Envy, Enemy},
+    events::EnemyDeathEvent,
     GameAssets, GameState,
 };
 
@@ -35,11 +37,23 @@ fn spawn_envyes(
 
 fn despawn_envyes(
     mut commands: Commands,
-    mut q_envyes: Query<(Entity, &Pressure, &mut EnvyState)>,
+    mut q_envyes: Query<(Entity, &Pressure, &mut EnvyState, &GlobalTransform, &Enemy)>,
+    mut death_events: EventWriter<EnemyDeathEvent>,
+    mut sound_events: EventWriter<PlaySound>,
+    game_assets: Res<GameAssets>,
 ) {
-    for (entity, envy_pressure, mut envy_state) in q_envyes.iter_mut() {
+    for (entity, envy_pressure, mut envy_state, transform, enemy) in q_envyes.iter_mut() {
         if envy_pressure.current >= envy_pressure.max && *envy_state != EnvyState::Dying {
             *envy_state = EnvyState::Dying;
+            sound_events.send(PlaySound {
+                clip: game_assets.brick_cracked_sound.clone(),
+                position: Some(transform.translation().truncate()),
+                ..default()
+            });
+            death_events.send(EnemyDeathEvent {
+                enemy: *enemy,
+                pos: transform.translation().truncate(),
+            });
             commands.entity(entity).despawn_recursive();
         }
     }
@@ -2,9 +2,11 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
 use crate::{
+    audio::PlaySound,
     brick::stats::{Pressure, BRICK_MASS},
     collision_group::{GROUP_ENEMY, GROUP_SENSOR_DEAD_ZONE},
     enemy::{gluttony::Gluttony, Enemy},
+    events::EnemyDeathEvent,
     GameAssets, GameState,
 };
 
@@ -41,13 +43,32 @@ fn spawn_gluttonyes(
 
 fn despawn_gluttonyes(
     mut commands: Commands,
-    mut q_gluttonyes: Query<(Entity, &Pressure, &mut GluttonyState)>,
+    mut q_gluttonyes: Query<(
+        Entity,
+        &Pressure,
+        &mut GluttonyState,
+        &GlobalTransform,
+        &Enemy,
+    )>,
+    mut death_events: EventWriter<EnemyDeathEvent>,
+    mut sound_events: EventWriter<PlaySound>,
+    game_assets: Res<GameAssets>,
 ) {
-    for (entity, gluttony_pressure, mut gluttony_state) in q_gluttonyes.iter_mut() {
+    for (entity, gluttony_pressure, mut gluttony_state, transform, enemy) in q_gluttonyes.iter_mut()
+    {
         if gluttony_pressure.current >= gluttony_pressure.max
             && *gluttony_state != GluttonyState::Dying
         {
             *gluttony_state = GluttonyState::Dying;
+            sound_events.send(PlaySound {
+                clip: game_assets.brick_cracked_sound.clone(),
+                position: Some(transform.translation().truncate()),
+                ..default()
+            });
+            death_events.send(EnemyDeathEvent {
+                enemy: *enemy,
+                pos: transform.translation().truncate(),
+            });
             commands.entity(entity).despawn_recursive();
         }
     }
@@ -2,12 +2,14 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
 use crate::{
+    audio::PlaySound,
     brick::stats::{Pressure, BRICK_MASS},
     collision_group::{GROUP_ENEMY, GROUP_SENSOR_DEAD_ZONE},
     enemy::{
         greed::{Greed, GREED_RADIUS},
         Enemy,
     },
+    events::EnemyDeathEvent,
     GameAssets, GameState,
 };
 
@@ -38,11 +40,23 @@ fn spawn_greedes(
 
 fn despawn_greedes(
     mut commands: Commands,
-    mut q_greedes: Query<(Entity, &Pressure, &mut GreedState)>,
+    mut q_greedes: Query<(Entity, &Pressure, &mut GreedState, &GlobalTransform, &Enemy)>,
+    mut death_events: EventWriter<EnemyDeathEvent>,
+    mut sound_events: EventWriter<PlaySound>,
+    game_assets: Res<GameAssets>,
 ) {
-    for (entity, greed_pressure, mut greed_state) in q_greedes.iter_mut() {
+    for (entity, greed_pressure, mut greed_state, transform, enemy) in q_greedes.iter_mut() {
         if greed_pressure.current >= greed_pressure.max && *greed_state != GreedState::Dying {
             *greed_state = GreedState::Dying;
+            sound_events.send(PlaySound {
+                clip: game_assets.brick_cracked_sound.clone(),
+                position: Some(transform.translation().truncate()),
+                ..default()
+            });
+            death_events.send(EnemyDeathEvent {
+                enemy: *enemy,
+                pos: transform.translation().truncate(),
+            });
             commands.entity(entity).despawn_recursive();
         }
     }
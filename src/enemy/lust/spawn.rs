@@ -2,9 +2,11 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
 use crate::{
+    audio::PlaySound,
     brick::stats::{Pressure, BRICK_MASS},
     collision_group::{GROUP_ENEMY, GROUP_SENSOR_DEAD_ZONE},
     enemy::{lust::Lust, Enemy},
+    events::EnemyDeathEvent,
     GameAssets, GameState,
 };
 
@@ -35,11 +37,23 @@ fn spawn_lustes(
 
 fn despawn_lustes(
     mut commands: Commands,
-    mut q_lustes: Query<(Entity, &Pressure, &mut LustState)>,
+    mut q_lustes: Query<(Entity, &Pressure, &mut LustState, &GlobalTransform, &Enemy)>,
+    mut death_events: EventWriter<EnemyDeathEvent>,
+    mut sound_events: EventWriter<PlaySound>,
+    game_assets: Res<GameAssets>,
 ) {
-    for (entity, lust_pressure, mut lust_state) in q_lustes.iter_mut() {
+    for (entity, lust_pressure, mut lust_state, transform, enemy) in q_lustes.iter_mut() {
         if lust_pressure.current >= lust_pressure.max && *lust_state != LustState::Dying {
             *lust_state = LustState::Dying;
+            sound_events.send(PlaySound {
+                clip: game_assets.brick_cracked_sound.clone(),
+                position: Some(transform.translation().truncate()),
+                ..default()
+            });
+            death_events.send(EnemyDeathEvent {
+                enemy: *enemy,
+                pos: transform.translation().truncate(),
+            });
             commands.entity(entity).despawn_recursive();
         }
     }
@@ -4,7 +4,8 @@ use bevy_rapier2d::prelude::*;
 use crate::{
     ball::DamageCoefficient,
     brick::Brick,
-    events::{Damage, DamageEvent},
+    enemy::CONTACT_DAMAGE,
+    events::{Damage, DamageEvent, DamageKind},
     world::map::room::control::ChooseState,
     GameState,
 };
@@ -55,11 +56,11 @@ fn handle_ball_collision_event(
                 let b_parent = q_parent.get(*entity_b).unwrap().get();
 
                 if q_brick.contains(b_parent) {
-                    let brick_entity = q_brick.get_single().unwrap();
                     damage_events.send(DamageEvent {
                         offender: a_parent,
-                        victim: brick_entity,
-                        damage: Damage::new(20.0),
+                        victim: b_parent,
+                        damage: Damage::new(CONTACT_DAMAGE),
+                        kind: DamageKind::Contact,
                     });
                 } else {
                     let b_damage_coe = q_damage_coefficient.get(b_parent).unwrap();
@@ -68,6 +69,7 @@ fn handle_ball_collision_event(
                         offender: b_parent,
                         victim: a_parent,
                         damage: Damage::new(b_damage.value * b_damage_coe.0),
+                        kind: DamageKind::Ball,
                     });
                     pride_hit_events.send(PrideHitEvent(a_parent));
                 }
@@ -77,11 +79,11 @@ fn handle_ball_collision_event(
                 let a_parent = q_parent.get(*entity_a).unwrap().get();
                 let b_parent = q_parent.get(*entity_b).unwrap().get();
                 if q_brick.contains(a_parent) {
-                    let brick_entity = q_brick.get_single().unwrap();
                     damage_events.send(DamageEvent {
                         offender: b_parent,
-                        victim: brick_entity,
-                        damage: Damage::new(20.0),
+                        victim: a_parent,
+                        damage: Damage::new(CONTACT_DAMAGE),
+                        kind: DamageKind::Contact,
                     });
                 } else {
                     let a_damage_coe = q_damage_coefficient.get(a_parent).unwrap();
@@ -90,6 +92,7 @@ fn handle_ball_collision_event(
                         offender: a_parent,
                         victim: b_parent,
                         damage: Damage::new(a_damage.value * a_damage_coe.0),
+                        kind: DamageKind::Ball,
                     });
                     pride_hit_events.send(PrideHitEvent(b_parent));
                 }
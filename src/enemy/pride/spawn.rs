@@ -2,9 +2,11 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
 use crate::{
+    audio::PlaySound,
     brick::stats::{Pressure, BRICK_MASS},
     collision_group::{GROUP_ENEMY, GROUP_SENSOR_DEAD_ZONE},
     enemy::{pride::Pride, Enemy},
+    events::EnemyDeathEvent,
     GameAssets, GameState,
 };
 
@@ -35,11 +37,23 @@ fn spawn_pridees(
 
 fn despawn_pridees(
     mut commands: Commands,
-    mut q_pridees: Query<(Entity, &Pressure, &mut PrideState)>,
+    mut q_pridees: Query<(Entity, &Pressure, &mut PrideState, &GlobalTransform, &Enemy)>,
+    mut death_events: EventWriter<EnemyDeathEvent>,
+    mut sound_events: EventWriter<PlaySound>,
+    game_assets: Res<GameAssets>,
 ) {
-    for (entity, pride_pressure, mut pride_state) in q_pridees.iter_mut() {
+    for (entity, pride_pressure, mut pride_state, transform, enemy) in q_pridees.iter_mut() {
         if pride_pressure.current >= pride_pressure.max && *pride_state != PrideState::Dying {
             *pride_state = PrideState::Dying;
+            sound_events.send(PlaySound {
+                clip: game_assets.brick_cracked_sound.clone(),
+                position: Some(transform.translation().truncate()),
+                ..default()
+            });
+            death_events.send(EnemyDeathEvent {
+                enemy: *enemy,
+                pos: transform.translation().truncate(),
+            });
             commands.entity(entity).despawn_recursive();
         }
     }
@@ -3,10 +3,10 @@ use bevy_rapier2d::prelude::*;
 
 use crate::{
     audio::PlaySound,
-    audio::Volume,
     brick::stats::{Pressure, BRICK_MASS},
     collision_group::{GROUP_ENEMY, GROUP_SENSOR_DEAD_ZONE},
     enemy::{sloth::Sloth, Enemy},
+    events::EnemyDeathEvent,
     GameAssets, GameState,
 };
 
@@ -37,19 +37,23 @@ fn spawn_slothes(
 
 fn despawn_slothes(
     mut commands: Commands,
-    mut q_slothes: Query<(Entity, &Pressure, &mut SlothState)>,
+    mut q_slothes: Query<(Entity, &Pressure, &mut SlothState, &GlobalTransform, &Enemy)>,
+    mut death_events: EventWriter<EnemyDeathEvent>,
     mut sound_events: EventWriter<PlaySound>,
     game_assets: Res<GameAssets>,
-    volume: Res<Volume>,
 ) {
-    for (entity, sloth_pressure, mut sloth_state) in q_slothes.iter_mut() {
+    for (entity, sloth_pressure, mut sloth_state, transform, enemy) in q_slothes.iter_mut() {
         if sloth_pressure.current >= sloth_pressure.max && *sloth_state != SlothState::Dying {
             *sloth_state = SlothState::Dying;
             sound_events.send(PlaySound {
                 clip: game_assets.brick_cracked_sound.clone(),
-                volume: volume.0 as f64,
+                position: Some(transform.translation().truncate()),
                 ..default()
             });
+            death_events.send(EnemyDeathEvent {
+                enemy: *enemy,
+                pos: transform.translation().truncate(),
+            });
             commands.entity(entity).despawn_recursive();
         }
     }
@@ -2,9 +2,11 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
 use crate::{
+    audio::PlaySound,
     brick::stats::{Pressure, BRICK_MASS},
     collision_group::{GROUP_ENEMY, GROUP_SENSOR_DEAD_ZONE},
     enemy::{wrath::Wrath, Enemy},
+    events::EnemyDeathEvent,
     GameAssets, GameState,
 };
 
@@ -35,11 +37,23 @@ fn spawn_wrathes(
 
 fn despawn_wrathes(
     mut commands: Commands,
-    mut q_wrathes: Query<(Entity, &Pressure, &mut WrathState)>,
+    mut q_wrathes: Query<(Entity, &Pressure, &mut WrathState, &GlobalTransform, &Enemy)>,
+    mut death_events: EventWriter<EnemyDeathEvent>,
+    mut sound_events: EventWriter<PlaySound>,
+    game_assets: Res<GameAssets>,
 ) {
-    for (entity, wrath_pressure, mut wrath_state) in q_wrathes.iter_mut() {
+    for (entity, wrath_pressure, mut wrath_state, transform, enemy) in q_wrathes.iter_mut() {
         if wrath_pressure.current >= wrath_pressure.max && *wrath_state != WrathState::Dying {
             *wrath_state = WrathState::Dying;
+            sound_events.send(PlaySound {
+                clip: game_assets.brick_cracked_sound.clone(),
+                position: Some(transform.translation().truncate()),
+                ..default()
+            });
+            death_events.send(EnemyDeathEvent {
+                enemy: *enemy,
+                pos: transform.translation().truncate(),
+            });
             commands.entity(entity).despawn_recursive();
         }
     }
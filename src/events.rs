@@ -1,6 +1,11 @@
 use bevy::prelude::*;
 
-use crate::{brick::stats::Pressure, GameState};
+use crate::{
+    brick::{abilities::Dash, stats::Pressure, Brick},
+    difficulty::DifficultyScaling,
+    enemy::Enemy,
+    GameState,
+};
 
 #[derive(Component, Clone, Copy, PartialEq, PartialOrd)]
 pub struct Damage {
@@ -13,31 +18,86 @@ impl Damage {
     }
 }
 
+// 伤害的来源，难度按来源调整砖块受到的伤害
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DamageKind {
+    // 敌人碰到砖块
+    Contact,
+    // 球击中敌人
+    Ball,
+    // 球掉进底部的删除区域
+    BallLost,
+    // 主动道具
+    Ability,
+}
+
 #[derive(Event)]
 pub struct DamageEvent {
     pub offender: Entity,
     pub victim: Entity,
     pub damage: Damage,
+    pub kind: DamageKind,
+}
+
+// 敌人压力满了被消灭
+#[derive(Event)]
+pub struct EnemyDeathEvent {
+    pub enemy: Enemy,
+    pub pos: Vec2,
+}
+
+// 球掉进底部的删除区域
+#[derive(Event)]
+pub struct BallLostEvent {
+    pub ball: Entity,
 }
 
+// 球从砖块上发射出去
+#[derive(Event)]
+pub struct BallShotEvent;
+
 pub struct EventPlugin;
 
 impl Plugin for EventPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<DamageEvent>().add_systems(
-            Update,
-            (handle_damage_events).run_if(in_state(GameState::Gaming)),
-        );
+        app.add_event::<DamageEvent>()
+            .add_event::<EnemyDeathEvent>()
+            .add_event::<BallLostEvent>()
+            .add_event::<BallShotEvent>()
+            .add_systems(
+                Update,
+                (handle_damage_events).run_if(in_state(GameState::Gaming)),
+            );
     }
 }
 
 fn handle_damage_events(
     mut events: EventReader<DamageEvent>,
     mut q_pressure: Query<&mut Pressure>,
+    q_dash: Query<&Dash>,
+    q_brick: Query<(), With<Brick>>,
+    scaling: DifficultyScaling,
 ) {
+    let modifiers = scaling.modifiers();
     for event in events.read() {
+        // 冲刺无敌期间不受敌人的接触伤害
+        let invulnerable = q_dash
+            .get(event.victim)
+            .is_ok_and(|dash| dash.is_invulnerable());
+        if invulnerable && event.kind == DamageKind::Contact {
+            continue;
+        }
+        // 砖块受到的接触伤害和漏球伤害按难度调整
+        let mut damage = event.damage.value;
+        if q_brick.contains(event.victim) {
+            damage *= match event.kind {
+                DamageKind::Contact => modifiers.contact_damage,
+                DamageKind::BallLost => modifiers.ball_lost_damage,
+                DamageKind::Ball | DamageKind::Ability => 1.0,
+            };
+        }
         if let Ok(mut pressure) = q_pressure.get_mut(event.victim) {
-            pressure.current = (pressure.current + event.damage.value).clamp(0.0, pressure.max);
+            pressure.current = (pressure.current + damage).clamp(0.0, pressure.max);
             // println!("pressure: {}", pressure.current);
         }
     }
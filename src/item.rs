@@ -1,29 +1,116 @@
 mod collision;
 mod init;
 mod pickup;
+mod pool;
+
+pub mod active;
+
+pub mod effects;
 
 pub mod sapwn;
 
+pub mod synergy;
+
 use bevy::prelude::*;
+use bevy::utils::HashMap;
 use serde::Deserialize;
 
-#[derive(Component, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Item {
-    Glue,
-    Placebo,
-    Schoolbag,
-    Wheel,
+use active::ActiveDef;
+use effects::ItemEffect;
+use synergy::SynergyDef;
+
+pub use pool::{LockedItems, PoolKind};
+
+// 道具在注册表里的编号，道具本身的数据都在 items.ron 中
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Item(usize);
+
+#[cfg(test)]
+impl Item {
+    pub(crate) fn new(index: usize) -> Self {
+        Self(index)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Rarity {
+    #[default]
+    Common,
+    Rare,
+    Legendary,
+}
+
+impl Rarity {
+    // 拾取通知里标题和边框的颜色
+    pub fn color(&self) -> Color {
+        match self {
+            Rarity::Common => Color::WHITE,
+            Rarity::Rare => Color::srgb(0.4, 0.7, 1.0),
+            Rarity::Legendary => Color::srgb(1.0, 0.8, 0.2),
+        }
+    }
 }
 
-#[derive(Component, Deserialize, Debug, Clone, Copy)]
-pub struct ItemEntity {
-    pub item: Item,
+#[derive(Deserialize, Debug, Clone)]
+pub struct ItemDef {
+    pub id: String,
+    // 名称和描述是本地化文本的键
+    pub name: String,
+    pub description: String,
+    pub icon: String,
+    // 图标的染色，没有专门美术资源的道具可以复用其他图标
+    #[serde(default)]
+    pub tint: Option<(f32, f32, f32)>,
+    #[serde(default)]
+    pub rarity: Rarity,
     pub weight: usize,
+    // 会出现在哪些道具池里
+    pub pools: Vec<PoolKind>,
+    // 组合条件里按标签匹配，例如 "ball"
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub effects: Vec<ItemEffect>,
+    // 主动道具，按键使用
+    #[serde(default)]
+    pub active: Option<ActiveDef>,
 }
 
-#[derive(Resource, Deserialize, Debug)]
-pub struct ItemPool {
-    pub pool: Vec<ItemEntity>,
+// 所有道具的定义，启动时从 items.ron 读取
+#[derive(Resource, Default)]
+pub struct ItemRegistry {
+    defs: Vec<ItemDef>,
+    icons: Vec<Handle<Image>>,
+    ids: HashMap<String, Item>,
+    synergies: Vec<SynergyDef>,
+}
+
+impl ItemDef {
+    pub fn tint_color(&self) -> Color {
+        self.tint
+            .map_or(Color::WHITE, |(r, g, b)| Color::srgb(r, g, b))
+    }
+}
+
+impl ItemRegistry {
+    pub fn get(&self, item: Item) -> &ItemDef {
+        &self.defs[item.0]
+    }
+
+    pub fn icon(&self, item: Item) -> Handle<Image> {
+        self.icons[item.0].clone()
+    }
+
+    pub fn find(&self, id: &str) -> Option<Item> {
+        self.ids.get(id).copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Item, &ItemDef)> {
+        self.defs
+            .iter()
+            .enumerate()
+            .map(|(index, def)| (Item(index), def))
+    }
 }
 
 pub struct ItemPlugin;
@@ -35,6 +122,10 @@ impl Plugin for ItemPlugin {
             sapwn::SpawnPlugin,
             collision::CollisionPlugin,
             pickup::PickUpPlugin,
+            effects::EffectsPlugin,
+            pool::PoolPlugin,
+            active::ActivePlugin,
+            synergy::SynergyPlugin,
         ));
     }
 }
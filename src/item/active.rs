@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    ball::Ball,
+    brick::{
+        input::PlayerInput, inventory::Inventory, item_collection::AddItemEvent, BallInHand, Brick,
+    },
+    enemy::Enemy,
+    events::{Damage, DamageEvent, DamageKind},
+    world::map::room::control::ChooseState,
+    GameState,
+};
+
+use super::{effects::SplitBall, Item, ItemRegistry};
+
+pub struct ActivePlugin;
+
+impl Plugin for ActivePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveItemSlot>()
+            .init_resource::<SlowTime>()
+            .add_systems(OnEnter(GameState::Gaming), reset_active_slot)
+            .add_systems(OnEnter(ChooseState::Choosing), charge_on_room_cleared)
+            .add_systems(
+                Update,
+                (
+                    equip_active_item,
+                    charge_on_damage,
+                    use_active_item,
+                    slow_time,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Gaming)),
+            )
+            .add_systems(OnExit(GameState::Gaming), cancel_slow_time);
+    }
+}
+
+// 主动道具的定义，写在 items.ron 的 active 字段里
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct ActiveDef {
+    pub ability: ActiveAbility,
+    // 充满需要的格数，清空一个房间充一格
+    pub charges: u32,
+    // 球对敌人造成这么多伤害也充一格，None 表示只能靠清房间充能
+    #[serde(default)]
+    pub damage_per_charge: Option<f32>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum ActiveAbility {
+    // 对场上所有敌人造成伤害
+    PressureWave { damage: f32 },
+    // 放慢时间，scale 是游戏速度，seconds 是真实时间
+    SlowTime { scale: f32, seconds: f32 },
+    // 把场上的球全部收回背包
+    RecallBalls,
+}
+
+// 砖块身上的主动道具槽，同一时间只能拿一个主动道具
+#[derive(Resource, Default)]
+pub struct ActiveItemSlot {
+    pub item: Option<Item>,
+    pub charge: f32,
+}
+
+impl ActiveItemSlot {
+    // 当前充能的比例，没有主动道具时为 0
+    pub fn fraction(&self, registry: &ItemRegistry) -> f32 {
+        self.def(registry)
+            .map_or(0.0, |active| self.charge / active.charges as f32)
+    }
+
+    fn def(&self, registry: &ItemRegistry) -> Option<ActiveDef> {
+        self.item.and_then(|item| registry.get(item).active)
+    }
+
+    fn add_charge(&mut self, registry: &ItemRegistry, amount: f32) {
+        if let Some(active) = self.def(registry) {
+            self.charge = (self.charge + amount).min(active.charges as f32);
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+struct SlowTime(Option<(Timer, f32)>);
+
+fn reset_active_slot(mut slot: ResMut<ActiveItemSlot>) {
+    *slot = ActiveItemSlot::default();
+}
+
+// 拾取新的主动道具会替换掉旧的，新道具自带满充能
+fn equip_active_item(
+    mut add_item_events: EventReader<AddItemEvent>,
+    mut slot: ResMut<ActiveItemSlot>,
+    registry: Res<ItemRegistry>,
+) {
+    for &AddItemEvent(item) in add_item_events.read() {
+        if let Some(active) = registry.get(item).active {
+            slot.item = Some(item);
+            slot.charge = active.charges as f32;
+        }
+    }
+}
+
+fn charge_on_room_cleared(mut slot: ResMut<ActiveItemSlot>, registry: Res<ItemRegistry>) {
+    slot.add_charge(&registry, 1.0);
+}
+
+fn charge_on_damage(
+    mut damage_events: EventReader<DamageEvent>,
+    mut slot: ResMut<ActiveItemSlot>,
+    registry: Res<ItemRegistry>,
+    q_ball: Query<(), With<Ball>>,
+    q_enemy: Query<(), With<Enemy>>,
+) {
+    let Some(damage_per_charge) = slot.def(&registry).and_then(|a| a.damage_per_charge) else {
+        damage_events.clear();
+        return;
+    };
+    let damage: f32 = damage_events
+        .read()
+        .filter(|event| q_ball.contains(event.offender) && q_enemy.contains(event.victim))
+        .map(|event| event.damage.value)
+        .sum();
+    if damage > 0.0 {
+        slot.add_charge(&registry, damage / damage_per_charge);
+    }
+}
+
+// 主动道具槽是共享的，哪个玩家按下就由哪个砖块使用
+fn use_active_item(
+    mut commands: Commands,
+    mut q_brick: Query<(Entity, &PlayerInput, &mut Inventory), With<Brick>>,
+    mut slot: ResMut<ActiveItemSlot>,
+    registry: Res<ItemRegistry>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut slow_time: ResMut<SlowTime>,
+    q_enemy: Query<Entity, With<Enemy>>,
+    q_ball: Query<(Entity, &Ball, Has<SplitBall>), Without<BallInHand>>,
+) {
+    let Some((brick, _, mut inventory)) = q_brick
+        .iter_mut()
+        .find(|(_, player_input, _)| player_input.use_item)
+    else {
+        return;
+    };
+    let Some(active) = slot.def(&registry) else {
+        return;
+    };
+    if slot.charge < active.charges as f32 {
+        return;
+    }
+    slot.charge = 0.0;
+
+    match active.ability {
+        ActiveAbility::PressureWave { damage } => {
+            for enemy in q_enemy.iter() {
+                damage_events.send(DamageEvent {
+                    offender: brick,
+                    victim: enemy,
+                    damage: Damage::new(damage),
+                    kind: DamageKind::Ability,
+                });
+            }
+        }
+        ActiveAbility::SlowTime { scale, seconds } => {
+            slow_time.0 = Some((Timer::from_seconds(seconds, TimerMode::Once), scale));
+        }
+        ActiveAbility::RecallBalls => {
+            for (entity, ball, is_split) in q_ball.iter() {
+                // 分裂出来的球直接消失，背包满了的球留在场上
+                if is_split || inventory.push(*ball).is_ok() {
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+        }
+    }
+}
+
+// 只在正常速度时放慢，避免覆盖顿帧；计时用真实时间
+fn slow_time(
+    mut slow_time: ResMut<SlowTime>,
+    mut time: ResMut<Time<Virtual>>,
+    real_time: Res<Time<Real>>,
+) {
+    let Some((timer, scale)) = slow_time.0.as_mut() else {
+        return;
+    };
+    timer.tick(real_time.delta());
+    if timer.finished() {
+        if time.relative_speed() == *scale {
+            time.set_relative_speed(1.0);
+        }
+        slow_time.0 = None;
+    } else if time.relative_speed() == 1.0 {
+        time.set_relative_speed(*scale);
+    }
+}
+
+fn cancel_slow_time(mut slow_time: ResMut<SlowTime>, mut time: ResMut<Time<Virtual>>) {
+    if slow_time.0.take().is_some() {
+        time.set_relative_speed(1.0);
+    }
+}
@@ -0,0 +1,431 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_rapier2d::prelude::Velocity;
+use serde::Deserialize;
+
+use crate::{
+    ball::{Ball, OriginalVel},
+    brick::{
+        brick_ball::{spawn_ball, BRICK_BALL_SIZE},
+        character::Passives,
+        inventory::Inventory,
+        item_collection::{AddItemEvent, ItemCollection},
+        stats::{ModifierOp, Pressure, Stat, StatModifier, StatModifiers},
+        Brick,
+    },
+    enemy::Enemy,
+    events::{BallLostEvent, DamageEvent, EnemyDeathEvent},
+    world::map::room::loading::LoadingState,
+    GameAssets, GameState,
+};
+
+use super::{
+    synergy::{ActiveSynergies, Synergy, SynergyUnlockedEvent},
+    Item, ItemRegistry,
+};
+
+pub struct EffectsPlugin;
+
+impl Plugin for EffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ApplyEffectEvent>()
+            .add_systems(
+                Update,
+                (
+                    (
+                        on_pickup,
+                        on_passives_changed,
+                        on_synergy_unlocked,
+                        on_ball_hit,
+                        on_enemy_killed,
+                        on_ball_lost,
+                        on_pressure_threshold,
+                        on_timer,
+                    ),
+                    apply_effects,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Gaming)),
+            )
+            .add_systems(OnEnter(LoadingState::FadeIn), on_room_entered);
+    }
+}
+
+// 效果的触发时机
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Trigger {
+    OnPickup,
+    // 球击中敌人
+    OnBallHit,
+    OnEnemyKilled,
+    // 球掉进底部的删除区域
+    OnBallLost,
+    OnRoomEntered,
+    // 压力比例从下往上越过阈值（0.0 ~ 1.0）
+    OnPressureAbove(f32),
+    // 每隔多少秒触发一次
+    Every(f32),
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    // 给砖块加上属性修正，来源是这个道具
+    Modify { stat: Stat, op: ModifierOp },
+    RelievePressure(f32),
+    ExpandInventory(usize),
+    GiveBalls { ball: Ball, count: usize },
+    // 击中的球分裂出 count 个球，每个偏转 spread 度
+    SplitBall { count: usize, spread: f32 },
+    // 护盾充满，抵挡一次漏球
+    Shield,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct ItemEffect {
+    pub trigger: Trigger,
+    pub action: Action,
+}
+
+// 效果的来源：单个道具或者道具组合
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EffectSource {
+    Item(Item),
+    Synergy(Synergy),
+}
+
+impl ItemRegistry {
+    pub fn effects(&self, source: EffectSource) -> &[ItemEffect] {
+        match source {
+            EffectSource::Item(item) => &self.get(item).effects,
+            EffectSource::Synergy(synergy) => &self.synergy(synergy).effects,
+        }
+    }
+
+    // 来源名称的本地化文本键
+    pub fn source_name(&self, source: EffectSource) -> &str {
+        match source {
+            EffectSource::Item(item) => &self.get(item).name,
+            EffectSource::Synergy(synergy) => &self.synergy(synergy).name,
+        }
+    }
+
+    // 在 items.ron 里定义的、符合触发条件的效果
+    fn actions<'a>(
+        &'a self,
+        source: EffectSource,
+        trigger: impl Fn(&Trigger) -> bool + 'a,
+    ) -> impl Iterator<Item = Action> + 'a {
+        self.effects(source)
+            .iter()
+            .filter(move |effect| trigger(&effect.trigger))
+            .map(|effect| effect.action)
+    }
+}
+
+// 护盾剩余次数
+#[derive(Component, Default)]
+pub struct Shield {
+    pub charges: usize,
+}
+
+// 分裂出来的球，不会再次分裂
+#[derive(Component)]
+pub struct SplitBall;
+
+#[derive(Event)]
+struct ApplyEffectEvent {
+    source: EffectSource,
+    action: Action,
+    // 触发效果的球
+    ball: Option<Entity>,
+    // 效果作用的砖块，None 表示所有砖块
+    brick: Option<Entity>,
+}
+
+// 已拥有的道具、已解锁的组合和每个砖块自己的被动
+#[derive(SystemParam)]
+struct Owned<'w, 's> {
+    collection: Res<'w, ItemCollection>,
+    synergies: Res<'w, ActiveSynergies>,
+    registry: Res<'w, ItemRegistry>,
+    q_passives: Query<'w, 's, (Entity, &'static Passives), With<Brick>>,
+}
+
+impl Owned<'_, '_> {
+    // 每个来源、它在各自列表里的序号和所属的砖块，同一个道具可能拥有多个
+    // 共享的道具和组合不属于某个砖块
+    fn sources(&self) -> impl Iterator<Item = (usize, EffectSource, Option<Entity>)> + '_ {
+        let items = self.collection.0.iter().enumerate();
+        let synergies = self.synergies.0.iter().enumerate();
+        let passives = self.q_passives.iter().flat_map(|(brick, passives)| {
+            passives
+                .0
+                .iter()
+                .enumerate()
+                .map(move |(index, &item)| (index, EffectSource::Item(item), Some(brick)))
+        });
+        items
+            .map(|(index, &item)| (index, EffectSource::Item(item), None))
+            .chain(synergies.map(|(index, &synergy)| (index, EffectSource::Synergy(synergy), None)))
+            .chain(passives)
+    }
+
+    // 符合触发条件的效果全部生效，brick 是触发效果的砖块
+    // 被动只在自己的砖块上生效，也只由自己的砖块触发
+    fn fire(
+        &self,
+        trigger: impl Fn(&Trigger) -> bool + Copy,
+        ball: Option<Entity>,
+        brick: Option<Entity>,
+        writer: &mut EventWriter<ApplyEffectEvent>,
+    ) {
+        for (_, source, owner) in self.sources() {
+            if owner.is_some() && brick.is_some() && owner != brick {
+                continue;
+            }
+            for action in self.registry.actions(source, trigger) {
+                writer.send(ApplyEffectEvent {
+                    source,
+                    action,
+                    ball,
+                    brick: owner.or(brick),
+                });
+            }
+        }
+    }
+}
+
+fn send_pickup_effects(
+    source: EffectSource,
+    brick: Option<Entity>,
+    registry: &ItemRegistry,
+    writer: &mut EventWriter<ApplyEffectEvent>,
+) {
+    for action in registry.actions(source, |trigger| *trigger == Trigger::OnPickup) {
+        writer.send(ApplyEffectEvent {
+            source,
+            action,
+            ball: None,
+            brick,
+        });
+    }
+}
+
+fn on_pickup(
+    mut add_item_events: EventReader<AddItemEvent>,
+    effects: Res<ItemRegistry>,
+    mut writer: EventWriter<ApplyEffectEvent>,
+) {
+    for &AddItemEvent(item) in add_item_events.read() {
+        send_pickup_effects(EffectSource::Item(item), None, &effects, &mut writer);
+    }
+}
+
+// 选完角色后被动道具的 OnPickup 效果只给自己的砖块
+fn on_passives_changed(
+    q_passives: Query<(Entity, &Passives), (With<Brick>, Changed<Passives>)>,
+    effects: Res<ItemRegistry>,
+    mut writer: EventWriter<ApplyEffectEvent>,
+) {
+    for (brick, passives) in q_passives.iter() {
+        for &item in passives.0.iter() {
+            send_pickup_effects(EffectSource::Item(item), Some(brick), &effects, &mut writer);
+        }
+    }
+}
+
+// 组合解锁时触发它的 OnPickup 效果
+fn on_synergy_unlocked(
+    mut unlocked_events: EventReader<SynergyUnlockedEvent>,
+    effects: Res<ItemRegistry>,
+    mut writer: EventWriter<ApplyEffectEvent>,
+) {
+    for &SynergyUnlockedEvent(synergy) in unlocked_events.read() {
+        send_pickup_effects(EffectSource::Synergy(synergy), None, &effects, &mut writer);
+    }
+}
+
+fn on_ball_hit(
+    mut damage_events: EventReader<DamageEvent>,
+    q_ball: Query<(), With<Ball>>,
+    q_enemy: Query<(), With<Enemy>>,
+    owned: Owned,
+    mut writer: EventWriter<ApplyEffectEvent>,
+) {
+    for event in damage_events.read() {
+        if q_ball.contains(event.offender) && q_enemy.contains(event.victim) {
+            owned.fire(
+                |trigger| *trigger == Trigger::OnBallHit,
+                Some(event.offender),
+                None,
+                &mut writer,
+            );
+        }
+    }
+}
+
+fn on_enemy_killed(
+    mut death_events: EventReader<EnemyDeathEvent>,
+    owned: Owned,
+    mut writer: EventWriter<ApplyEffectEvent>,
+) {
+    for _ in death_events.read() {
+        owned.fire(
+            |trigger| *trigger == Trigger::OnEnemyKilled,
+            None,
+            None,
+            &mut writer,
+        );
+    }
+}
+
+fn on_ball_lost(
+    mut ball_lost_events: EventReader<BallLostEvent>,
+    owned: Owned,
+    mut writer: EventWriter<ApplyEffectEvent>,
+) {
+    for event in ball_lost_events.read() {
+        owned.fire(
+            |trigger| *trigger == Trigger::OnBallLost,
+            Some(event.ball),
+            None,
+            &mut writer,
+        );
+    }
+}
+
+fn on_room_entered(owned: Owned, mut writer: EventWriter<ApplyEffectEvent>) {
+    owned.fire(
+        |trigger| *trigger == Trigger::OnRoomEntered,
+        None,
+        None,
+        &mut writer,
+    );
+}
+
+// 每个砖块的压力分别判断
+fn on_pressure_threshold(
+    q_pressure: Query<(Entity, &Pressure), (With<Brick>, Changed<Pressure>)>,
+    mut last_ratios: Local<HashMap<Entity, f32>>,
+    owned: Owned,
+    mut writer: EventWriter<ApplyEffectEvent>,
+) {
+    for (brick, pressure) in q_pressure.iter() {
+        let ratio = pressure.current / pressure.max;
+        let last = last_ratios.insert(brick, ratio).unwrap_or(0.0);
+        owned.fire(
+            |trigger| matches!(*trigger, Trigger::OnPressureAbove(threshold) if last < threshold && ratio >= threshold),
+            None,
+            Some(brick),
+            &mut writer,
+        );
+    }
+}
+
+// 计时器按 (来源, 来源的序号, 所属砖块, 效果序号) 区分
+fn on_timer(
+    mut timers: Local<HashMap<(EffectSource, usize, Option<Entity>, usize), Timer>>,
+    owned: Owned,
+    mut writer: EventWriter<ApplyEffectEvent>,
+    time: Res<Time>,
+) {
+    for (source_index, source, owner) in owned.sources() {
+        for (effect_index, effect) in owned.registry.effects(source).iter().enumerate() {
+            let Trigger::Every(seconds) = effect.trigger else {
+                continue;
+            };
+            let timer = timers
+                .entry((source, source_index, owner, effect_index))
+                .or_insert_with(|| Timer::from_seconds(seconds, TimerMode::Repeating));
+            timer.tick(time.delta());
+            for _ in 0..timer.times_finished_this_tick() {
+                writer.send(ApplyEffectEvent {
+                    source,
+                    action: effect.action,
+                    ball: None,
+                    brick: owner,
+                });
+            }
+        }
+    }
+}
+
+// 道具是所有玩家共享的，作用在砖块上的效果每个砖块都生效，被动和压力触发的效果只作用在对应的砖块上
+fn apply_effects(
+    mut commands: Commands,
+    mut events: EventReader<ApplyEffectEvent>,
+    mut q_brick: Query<
+        (
+            Entity,
+            &mut StatModifiers,
+            &mut Pressure,
+            &mut Shield,
+            &mut Inventory,
+        ),
+        With<Brick>,
+    >,
+    q_ball: Query<(&Ball, &Transform, &Velocity), Without<SplitBall>>,
+    assets: Res<GameAssets>,
+) {
+    for event in events.read() {
+        let bricks = q_brick
+            .iter_mut()
+            .filter(|(entity, ..)| event.brick.is_none_or(|brick| brick == *entity));
+        match event.action {
+            Action::Modify { stat, op } => {
+                for (_, mut modifiers, ..) in bricks {
+                    modifiers.add(StatModifier {
+                        stat,
+                        op,
+                        source: event.source,
+                    });
+                }
+            }
+            Action::RelievePressure(value) => {
+                for (_, _, mut pressure, ..) in bricks {
+                    pressure.current = (pressure.current - value).max(0.0);
+                }
+            }
+            Action::ExpandInventory(num) => {
+                for (.., mut inventory) in bricks {
+                    inventory.expansion(num);
+                }
+            }
+            Action::GiveBalls { ball, count } => {
+                for (.., mut inventory) in bricks {
+                    for _ in 0..count {
+                        if inventory.push(ball).is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+            Action::SplitBall { count, spread } => {
+                let Some(Ok((ball, transform, velocity))) = event.ball.map(|e| q_ball.get(e))
+                else {
+                    continue;
+                };
+                // 分裂的球左右交替偏转，越往后偏得越多
+                for i in 0..count {
+                    let side = if i % 2 == 0 { 1.0 } else { -1.0 };
+                    let angle = side * spread.to_radians() * (i / 2 + 1) as f32;
+                    let linvel = Vec2::from_angle(angle).rotate(velocity.linvel);
+                    // 沿新的方向挪开，不和原来的球以及对称的另一个分裂球重叠，否则下一步就会互相弹开
+                    let distance = BRICK_BALL_SIZE * (0.5 / angle.abs().sin()).clamp(1.0, 3.0);
+                    let pos =
+                        transform.translation.truncate() + linvel.normalize_or_zero() * distance;
+                    let entity = spawn_ball(&mut commands, &assets, *ball, pos, linvel, false);
+                    commands
+                        .entity(entity)
+                        .insert((SplitBall, OriginalVel(Velocity::linear(linvel))));
+                }
+            }
+            Action::Shield => {
+                for (_, _, _, mut shield, _) in bricks {
+                    shield.charges = 1;
+                }
+            }
+        }
+    }
+}
@@ -1,20 +1,63 @@
 use std::fs::File;
 
 use bevy::prelude::*;
+use bevy::utils::HashMap;
 use ron::{self, de::from_reader};
+use serde::Deserialize;
 
-use super::ItemPool;
+use super::{
+    pool::ItemPoolConfig,
+    synergy::{Requirement, SynergyDef},
+    Item, ItemDef, ItemRegistry,
+};
 
 pub struct InitPlugin;
 
 impl Plugin for InitPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, init_rooms);
+        app.init_resource::<ItemRegistry>()
+            .add_systems(Startup, init_items);
     }
 }
 
-fn init_rooms(mut commands: Commands) {
+// items.ron 的内容
+#[derive(Deserialize)]
+struct ItemCatalogue {
+    items: Vec<ItemDef>,
+    pools: ItemPoolConfig,
+    #[serde(default)]
+    synergies: Vec<SynergyDef>,
+}
+
+fn init_items(mut commands: Commands, asset_server: Res<AssetServer>) {
     let file = File::open("assets/items/items.ron").expect("Failed to open items.ron");
-    let item_pool: ItemPool = from_reader(file).expect("Unable to load items.ron");
-    commands.insert_resource(item_pool);
+    let catalogue: ItemCatalogue = from_reader(file).expect("Unable to load items.ron");
+
+    let mut ids = HashMap::new();
+    for (index, def) in catalogue.items.iter().enumerate() {
+        if ids.insert(def.id.clone(), Item(index)).is_some() {
+            panic!("duplicate item id in items.ron: {}", def.id);
+        }
+    }
+    for synergy in catalogue.synergies.iter() {
+        for requirement in synergy.requires.iter() {
+            if let Requirement::Item(id) = requirement {
+                if !ids.contains_key(id) {
+                    panic!("unknown item id in synergy {}: {}", synergy.id, id);
+                }
+            }
+        }
+    }
+    let icons = catalogue
+        .items
+        .iter()
+        .map(|def| asset_server.load(def.icon.clone()))
+        .collect();
+    commands.insert_resource(ItemRegistry {
+        defs: catalogue.items,
+        icons,
+        ids,
+        synergies: catalogue.synergies,
+    });
+    commands.insert_resource(catalogue.pools);
 }
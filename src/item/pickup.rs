@@ -1,13 +1,14 @@
-use bevy::prelude::*;
-use bevy_rapier2d::prelude::Friction;
+use bevy::{color::palettes::css::VIOLET, prelude::*};
 
 use crate::{
-    ball::Ball,
-    brick::{inventory::Inventory, item_collection::AddItemEvent, stats::Pressure, Brick, Speed},
-    ui::item_pickup_hint::{ItemPickupData, ShowItemPickupEvent},
+    brick::item_collection::AddItemEvent,
+    localization::Texts,
+    ui::notification::{Notification, NotificationKind, NotifyEvent},
     GameState,
 };
 
+use super::{synergy::SynergyUnlockedEvent, ItemRegistry};
+
 pub struct PickUpPlugin;
 
 impl Plugin for PickUpPlugin {
@@ -16,67 +17,33 @@ impl Plugin for PickUpPlugin {
     }
 }
 
+// 道具的效果由 effects 根据 items.ron 里的定义触发，这里只负责拾取提示
+// 道具组合解锁时在道具提示之后显示
 fn pickup(
-    mut events: EventWriter<ShowItemPickupEvent>,
+    mut events: EventWriter<NotifyEvent>,
     mut add_item_events_reader: EventReader<AddItemEvent>,
-    mut q_pressure: Query<&mut Pressure, With<Brick>>,
-    mut inventory: ResMut<Inventory>,
-    mut q_fraction: Query<&mut Friction, With<Brick>>,
-    mut q_speed: Query<&mut Speed, With<Brick>>,
+    mut synergy_events: EventReader<SynergyUnlockedEvent>,
+    registry: Res<ItemRegistry>,
+    texts: Res<Texts>,
 ) {
     for &AddItemEvent(item) in add_item_events_reader.read() {
-        match item {
-            super::Item::Glue => {
-                // println!("pickup ");
-                events.send(ShowItemPickupEvent(ItemPickupData {
-                    name: "胶水".into(),
-                    description: "增加摩檫力".into(),
-                }));
-
-                if let Ok(mut fric) = q_fraction.get_single_mut() {
-                    fric.coefficient += 20.0;
-                }
-            }
-            super::Item::Placebo => {
-                // println!("pickup ");
-                events.send(ShowItemPickupEvent(ItemPickupData {
-                    name: "安慰剂".into(),
-                    description: "减少压力提升压力上限".into(),
-                }));
-
-                if let Ok(mut pressure) = q_pressure.get_single_mut() {
-                    let mut current = pressure.current - 20.0;
-                    if current < 0.0 {
-                        current = 0.0
-                    }
-                    pressure.current = current;
-                    pressure.max += 20.0;
-                }
-            }
-            super::Item::Schoolbag => {
-                // println!("pickup ");
-                events.send(ShowItemPickupEvent(ItemPickupData {
-                    name: "书包".into(),
-                    description: "扩容！！！".into(),
-                }));
-
-                inventory.expansion(2);
-
-                if let Ok(_) = inventory.push(Ball::Tennis) {
-                    if let Ok(_) = inventory.push(Ball::Tennis) {}
-                }
-            }
-            super::Item::Wheel => {
-                // println!("pickup ");
-                events.send(ShowItemPickupEvent(ItemPickupData {
-                    name: "轮子".into(),
-                    description: "速度提升".into(),
-                }));
-
-                if let Ok(mut speed) = q_speed.get_single_mut() {
-                    speed.0 += 20.0;
-                }
-            }
-        }
+        let def = registry.get(item);
+        events.send(NotifyEvent(Notification {
+            kind: NotificationKind::Item,
+            title: texts.get(&def.name).into(),
+            description: texts.get(&def.description).into(),
+            icon: Some(ImageNode::new(registry.icon(item)).with_color(def.tint_color())),
+            color: def.rarity.color(),
+        }));
+    }
+    for &SynergyUnlockedEvent(synergy) in synergy_events.read() {
+        let def = registry.synergy(synergy);
+        events.send(NotifyEvent(Notification {
+            kind: NotificationKind::Synergy,
+            title: texts.get(&def.name).into(),
+            description: texts.get(&def.description).into(),
+            icon: None,
+            color: VIOLET.into(),
+        }));
     }
 }
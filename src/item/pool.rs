@@ -0,0 +1,372 @@
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    events::EnemyDeathEvent,
+    world::map::room::{
+        control::ChooseState, loading::LoadingState, select::SelectedRooms, RoomType,
+    },
+    GameState,
+};
+
+use super::{sapwn::SpawnItemEvent, Item, ItemRegistry, Rarity};
+
+pub struct PoolPlugin;
+
+impl Plugin for PoolPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ItemPools>()
+            .init_resource::<ItemPoolConfig>()
+            .init_resource::<LockedItems>()
+            .add_systems(OnEnter(GameState::Gaming), reset_pools)
+            .add_systems(OnEnter(LoadingState::FadeIn), spawn_room_items)
+            .add_systems(OnEnter(ChooseState::Choosing), spawn_boss_drop)
+            .add_systems(Update, enemy_drops.run_if(in_state(GameState::Gaming)));
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PoolKind {
+    Treasure,
+    Store,
+    Boss,
+    // 敌人被消灭时掉落
+    EnemyDrop,
+}
+
+impl PoolKind {
+    // 进入房间时摆出道具所用的道具池
+    fn for_room(room_type: RoomType) -> Option<Self> {
+        match room_type {
+            RoomType::Treasure => Some(PoolKind::Treasure),
+            RoomType::Store => Some(PoolKind::Store),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+pub struct TierWeights {
+    pub common: f32,
+    pub rare: f32,
+    pub legendary: f32,
+}
+
+impl TierWeights {
+    fn get(&self, rarity: Rarity) -> f32 {
+        match rarity {
+            Rarity::Common => self.common,
+            Rarity::Rare => self.rare,
+            Rarity::Legendary => self.legendary,
+        }
+    }
+}
+
+// 道具池的设置，在 items.ron 中
+#[derive(Resource, Deserialize, Debug, Clone)]
+pub struct ItemPoolConfig {
+    // 每个道具池各稀有度的基础权重
+    pub tiers: HashMap<PoolKind, TierWeights>,
+    // 每深入一个房间，各稀有度权重的变化
+    pub depth_shift: TierWeights,
+    // 连续这么多次只出普通道具之后，下一次必出稀有及以上
+    pub pity_rolls: u32,
+    pub enemy_drop_chance: f64,
+    // 商店一次摆出的道具数
+    pub store_items: usize,
+}
+
+impl Default for ItemPoolConfig {
+    fn default() -> Self {
+        Self {
+            tiers: HashMap::new(),
+            depth_shift: TierWeights::default(),
+            pity_rolls: 5,
+            enemy_drop_chance: 0.0,
+            store_items: 2,
+        }
+    }
+}
+
+impl ItemPoolConfig {
+    // rare_items 是难度对稀有和传说道具权重的倍率
+    fn tier_weight(
+        &self,
+        kind: PoolKind,
+        rarity: Rarity,
+        depth: usize,
+        pity: bool,
+        rare_items: f32,
+    ) -> f32 {
+        if pity && rarity == Rarity::Common {
+            return 0.0;
+        }
+        let base = self.tiers.get(&kind).copied().unwrap_or_default();
+        let weight = (base.get(rarity) + self.depth_shift.get(rarity) * depth as f32).max(0.0);
+        if rarity == Rarity::Common {
+            weight
+        } else {
+            weight * rare_items
+        }
+    }
+}
+
+// 还没有在局外成长里解锁的道具，不会从道具池里抽出
+#[derive(Resource, Default)]
+pub struct LockedItems(pub HashSet<Item>);
+
+// 本局已经出现过的道具和保底计数
+#[derive(Resource, Default)]
+pub struct ItemPools {
+    taken: HashSet<Item>,
+    pity: u32,
+    room: Option<RoomType>,
+}
+
+impl ItemPools {
+    // 先按权重选出稀有度，再在这个稀有度里按道具权重抽取
+    pub fn roll(
+        &mut self,
+        kind: PoolKind,
+        depth: usize,
+        registry: &ItemRegistry,
+        config: &ItemPoolConfig,
+        locked: &LockedItems,
+        rare_items: f32,
+    ) -> Option<Item> {
+        let mut rng = thread_rng();
+        let pity = self.pity >= config.pity_rolls;
+        let rarities = [Rarity::Common, Rarity::Rare, Rarity::Legendary];
+        let weights =
+            rarities.map(|rarity| config.tier_weight(kind, rarity, depth, pity, rare_items));
+        let rarity = WeightedIndex::new(weights)
+            .map_or(Rarity::Common, |dist| rarities[dist.sample(&mut rng)]);
+
+        // 选中的稀有度没有道具时，依次退到相邻的稀有度；所有道具都出过之后允许重复
+        let item = [false, true].into_iter().find_map(|allow_taken| {
+            fallback(rarity).into_iter().find_map(|rarity| {
+                let candidates = registry
+                    .iter()
+                    .filter(|(item, def)| {
+                        def.rarity == rarity
+                            && def.pools.contains(&kind)
+                            && !locked.0.contains(item)
+                            && (allow_taken || !self.taken.contains(item))
+                    })
+                    .collect::<Vec<_>>();
+                let dist = WeightedIndex::new(candidates.iter().map(|(_, def)| def.weight)).ok()?;
+                Some(candidates[dist.sample(&mut rng)].0)
+            })
+        })?;
+
+        if registry.get(item).rarity == Rarity::Common {
+            self.pity += 1;
+        } else {
+            self.pity = 0;
+        }
+        self.taken.insert(item);
+        Some(item)
+    }
+}
+
+// 稀有度没有道具时的替补顺序：先往下，再往上
+fn fallback(rarity: Rarity) -> [Rarity; 3] {
+    match rarity {
+        Rarity::Common => [Rarity::Common, Rarity::Rare, Rarity::Legendary],
+        Rarity::Rare => [Rarity::Rare, Rarity::Common, Rarity::Legendary],
+        Rarity::Legendary => [Rarity::Legendary, Rarity::Rare, Rarity::Common],
+    }
+}
+
+fn reset_pools(mut pools: ResMut<ItemPools>) {
+    *pools = ItemPools::default();
+}
+
+fn spawn_room_items(
+    mut pools: ResMut<ItemPools>,
+    selected_rooms: Res<SelectedRooms>,
+    config: Res<ItemPoolConfig>,
+    mut spawn_item_events: EventWriter<SpawnItemEvent>,
+) {
+    let Some(room) = selected_rooms.rooms.get(selected_rooms.index) else {
+        return;
+    };
+    pools.room = Some(room.room_type);
+    let Some(pool) = PoolKind::for_room(room.room_type) else {
+        return;
+    };
+    let count = if pool == PoolKind::Store {
+        config.store_items
+    } else {
+        1
+    };
+    // 多个道具横向排开
+    for i in 0..count {
+        let x = (i as f32 - (count as f32 - 1.0) / 2.0) * 120.0;
+        spawn_item_events.send(SpawnItemEvent {
+            pos: Vec2::new(x, 0.0),
+            pool,
+        });
+    }
+}
+
+// Boss 房间清空后掉落 Boss 道具
+fn spawn_boss_drop(pools: Res<ItemPools>, mut spawn_item_events: EventWriter<SpawnItemEvent>) {
+    if pools.room == Some(RoomType::Boss) {
+        spawn_item_events.send(SpawnItemEvent {
+            pos: Vec2::ZERO,
+            pool: PoolKind::Boss,
+        });
+    }
+}
+
+fn enemy_drops(
+    mut death_events: EventReader<EnemyDeathEvent>,
+    mut spawn_item_events: EventWriter<SpawnItemEvent>,
+    config: Res<ItemPoolConfig>,
+) {
+    let mut rng = thread_rng();
+    for event in death_events.read() {
+        if rng.gen_bool(config.enemy_drop_chance.clamp(0.0, 1.0)) {
+            spawn_item_events.send(SpawnItemEvent {
+                pos: event.pos,
+                pool: PoolKind::EnemyDrop,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::item::ItemDef;
+
+    fn def(id: &str, rarity: Rarity) -> ItemDef {
+        ItemDef {
+            id: id.to_string(),
+            name: String::new(),
+            description: String::new(),
+            icon: String::new(),
+            tint: None,
+            rarity,
+            weight: 1,
+            pools: vec![PoolKind::Treasure],
+            tags: Vec::new(),
+            effects: Vec::new(),
+            active: None,
+        }
+    }
+
+    fn registry(defs: Vec<ItemDef>) -> ItemRegistry {
+        ItemRegistry {
+            ids: defs
+                .iter()
+                .enumerate()
+                .map(|(index, def)| (def.id.clone(), Item(index)))
+                .collect(),
+            defs,
+            ..default()
+        }
+    }
+
+    fn config(common: f32, rare: f32) -> ItemPoolConfig {
+        ItemPoolConfig {
+            tiers: HashMap::from([(
+                PoolKind::Treasure,
+                TierWeights {
+                    common,
+                    rare,
+                    legendary: 0.0,
+                },
+            )]),
+            pity_rolls: 2,
+            ..default()
+        }
+    }
+
+    fn roll(
+        pools: &mut ItemPools,
+        registry: &ItemRegistry,
+        config: &ItemPoolConfig,
+    ) -> Option<Item> {
+        pools.roll(
+            PoolKind::Treasure,
+            0,
+            registry,
+            config,
+            &LockedItems::default(),
+            1.0,
+        )
+    }
+
+    #[test]
+    fn common_rolls_build_up_pity() {
+        let registry = registry(vec![def("a", Rarity::Common), def("b", Rarity::Common)]);
+        let config = config(1.0, 0.0);
+        let mut pools = ItemPools::default();
+        roll(&mut pools, &registry, &config);
+        roll(&mut pools, &registry, &config);
+        assert_eq!(pools.pity, 2);
+    }
+
+    #[test]
+    fn pity_forces_a_rare_item_and_resets() {
+        let registry = registry(vec![def("a", Rarity::Common), def("r", Rarity::Rare)]);
+        let config = config(1.0, 1.0);
+        let mut pools = ItemPools {
+            pity: config.pity_rolls,
+            ..default()
+        };
+        assert_eq!(
+            config.tier_weight(PoolKind::Treasure, Rarity::Common, 0, true, 1.0),
+            0.0
+        );
+        assert_eq!(roll(&mut pools, &registry, &config), registry.find("r"));
+        assert_eq!(pools.pity, 0);
+    }
+
+    #[test]
+    fn empty_rarity_falls_back_to_neighbour() {
+        // 只有稀有的权重，但道具池里只有普通道具
+        let registry = registry(vec![def("a", Rarity::Common)]);
+        let config = config(0.0, 1.0);
+        let mut pools = ItemPools::default();
+        assert_eq!(roll(&mut pools, &registry, &config), registry.find("a"));
+    }
+
+    #[test]
+    fn exhausted_pool_repeats_items() {
+        let registry = registry(vec![def("a", Rarity::Common)]);
+        let config = config(1.0, 0.0);
+        let mut pools = ItemPools::default();
+        assert_eq!(roll(&mut pools, &registry, &config), registry.find("a"));
+        assert_eq!(roll(&mut pools, &registry, &config), registry.find("a"));
+    }
+
+    #[test]
+    fn locked_items_are_never_rolled() {
+        let registry = registry(vec![def("a", Rarity::Common), def("b", Rarity::Common)]);
+        let config = config(1.0, 0.0);
+        let locked = LockedItems(registry.find("a").into_iter().collect());
+        let mut pools = ItemPools::default();
+        for _ in 0..10 {
+            let item = pools.roll(PoolKind::Treasure, 0, &registry, &config, &locked, 1.0);
+            assert_eq!(item, registry.find("b"));
+        }
+
+        let all_locked = LockedItems(registry.iter().map(|(item, _)| item).collect());
+        let item = pools.roll(PoolKind::Treasure, 0, &registry, &config, &all_locked, 1.0);
+        assert_eq!(item, None);
+    }
+
+    #[test]
+    fn rare_items_modifier_scales_only_rare_tiers() {
+        let config = config(1.0, 1.0);
+        let weight = |rarity| config.tier_weight(PoolKind::Treasure, rarity, 0, false, 2.0);
+        assert_eq!(weight(Rarity::Common), 1.0);
+        assert_eq!(weight(Rarity::Rare), 2.0);
+    }
+}
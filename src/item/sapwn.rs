@@ -1,16 +1,18 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
-use rand::distributions::WeightedIndex;
-use rand::prelude::*;
-
 use crate::{
+    brick::stats::BrickStats,
     collision_group::{GROUP_BRICK, GROUP_ITEM},
+    difficulty::DifficultyScaling,
     world::map::room::loading::RoomComponents,
-    GameAssets, GameState,
+    GameState,
 };
 
-use super::{Item, ItemPool};
+use super::{
+    pool::{ItemPoolConfig, ItemPools, LockedItems, PoolKind},
+    Item, ItemRegistry,
+};
 
 pub struct SpawnPlugin;
 
@@ -26,27 +28,31 @@ impl Plugin for SpawnPlugin {
 #[derive(Event)]
 pub struct SpawnItemEvent {
     pub pos: Vec2,
+    pub pool: PoolKind,
 }
 
 fn handle_spawn_item_event(
     mut commands: Commands,
-    mut item_pool: ResMut<ItemPool>,
+    mut pools: ResMut<ItemPools>,
     mut event_reader: EventReader<SpawnItemEvent>,
-    game_assets: Res<GameAssets>,
+    registry: Res<ItemRegistry>,
+    config: Res<ItemPoolConfig>,
+    brick_stats: Res<BrickStats>,
+    locked: Res<LockedItems>,
+    scaling: DifficultyScaling,
 ) {
-    for &SpawnItemEvent { pos } in event_reader.read() {
-        if item_pool.pool.len() != 0 {
-            let weights = item_pool
-                .pool
-                .iter()
-                .map(|item| item.weight)
-                .collect::<Vec<usize>>();
-            let dist = WeightedIndex::new(&weights).unwrap();
-            let mut rng = thread_rng();
-            let select_item = item_pool.pool.remove(dist.sample(&mut rng));
-            sapwn_item(&mut commands, pos, select_item.item, &game_assets);
-        } else {
-            sapwn_item(&mut commands, pos, Item::Schoolbag, &game_assets);
+    let rare_items = scaling.modifiers().rare_items;
+    for &SpawnItemEvent { pos, pool } in event_reader.read() {
+        match pools.roll(
+            pool,
+            brick_stats.current_room,
+            &registry,
+            &config,
+            &locked,
+            rare_items,
+        ) {
+            Some(item) => sapwn_item(&mut commands, pos, item, &registry),
+            None => warn!("item pool {:?} is empty", pool),
         }
     }
 }
@@ -54,16 +60,15 @@ fn handle_spawn_item_event(
 #[derive(Component)]
 pub struct SensorItem;
 
-pub fn sapwn_item(commands: &mut Commands, pos: Vec2, item: Item, game_assets: &Res<GameAssets>) {
-    let image = match item {
-        Item::Glue => game_assets.item_glue.clone(),
-        Item::Placebo => game_assets.item_placebo.clone(),
-        Item::Schoolbag => game_assets.item_schoolbag.clone(),
-        Item::Wheel => game_assets.item_wheel.clone(),
-    };
+pub fn sapwn_item(commands: &mut Commands, pos: Vec2, item: Item, registry: &ItemRegistry) {
+    let color = registry.get(item).tint_color();
     let entity = commands
         .spawn((
-            Sprite::from_image(image),
+            Sprite {
+                image: registry.icon(item),
+                color,
+                ..default()
+            },
             RigidBody::KinematicVelocityBased,
             Transform::from_translation(pos.extend(1.0)),
             item,
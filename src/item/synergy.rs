@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    brick::item_collection::{AddItemEvent, ItemCollection},
+    GameState,
+};
+
+use super::{effects::ItemEffect, ItemRegistry};
+
+pub struct SynergyPlugin;
+
+impl Plugin for SynergyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SynergyUnlockedEvent>()
+            .init_resource::<ActiveSynergies>()
+            .add_systems(OnEnter(GameState::Gaming), reset_synergies)
+            .add_systems(Update, check_synergies.run_if(in_state(GameState::Gaming)));
+    }
+}
+
+// 道具组合，在 items.ron 的 synergies 中定义
+#[derive(Deserialize, Debug, Clone)]
+pub struct SynergyDef {
+    pub id: String,
+    // 名称和描述是本地化文本的键
+    pub name: String,
+    pub description: String,
+    // 同时满足所有条件时解锁
+    pub requires: Vec<Requirement>,
+    // 和道具一样按触发时机生效，OnPickup 在解锁时触发
+    #[serde(default)]
+    pub effects: Vec<ItemEffect>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub enum Requirement {
+    // 拥有这个 id 的道具
+    Item(String),
+    // 拥有任意一个带有这个标签的道具
+    Tag(String),
+}
+
+impl Requirement {
+    fn is_met(&self, collection: &ItemCollection, registry: &ItemRegistry) -> bool {
+        match self {
+            Requirement::Item(id) => registry
+                .find(id)
+                .is_some_and(|item| collection.contains(item)),
+            Requirement::Tag(tag) => collection
+                .0
+                .iter()
+                .any(|&item| registry.get(item).tags.contains(tag)),
+        }
+    }
+}
+
+// 组合在注册表里的编号
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Synergy(pub(super) usize);
+
+impl ItemRegistry {
+    pub fn synergy(&self, synergy: Synergy) -> &SynergyDef {
+        &self.synergies[synergy.0]
+    }
+
+    pub fn synergies(&self) -> impl Iterator<Item = (Synergy, &SynergyDef)> {
+        self.synergies
+            .iter()
+            .enumerate()
+            .map(|(index, def)| (Synergy(index), def))
+    }
+}
+
+#[derive(Event)]
+pub struct SynergyUnlockedEvent(pub Synergy);
+
+// 本局已经解锁的组合
+#[derive(Resource, Default)]
+pub struct ActiveSynergies(pub Vec<Synergy>);
+
+fn reset_synergies(mut synergies: ResMut<ActiveSynergies>) {
+    synergies.0.clear();
+}
+
+// 每次拾取道具后检查是否凑齐了新的组合
+fn check_synergies(
+    mut add_item_events: EventReader<AddItemEvent>,
+    mut synergies: ResMut<ActiveSynergies>,
+    mut unlocked_events: EventWriter<SynergyUnlockedEvent>,
+    collection: Res<ItemCollection>,
+    registry: Res<ItemRegistry>,
+) {
+    if add_item_events.read().count() == 0 {
+        return;
+    }
+    for (synergy, def) in registry.synergies() {
+        if synergies.0.contains(&synergy) {
+            continue;
+        }
+        if def
+            .requires
+            .iter()
+            .all(|requirement| requirement.is_met(&collection, &registry))
+        {
+            synergies.0.push(synergy);
+            unlocked_events.send(SynergyUnlockedEvent(synergy));
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use std::fs::File;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use ron::de::from_reader;
+
+use crate::{settings::Language, GameAssets};
+
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Texts>()
+            .add_systems(Startup, load_texts)
+            .add_systems(
+                Update,
+                (
+                    switch_language.run_if(resource_changed::<Language>),
+                    update_localized_text,
+                    update_localized_font,
+                )
+                    .chain(),
+            );
+    }
+}
+
+const LANGUAGES: [Language; 2] = [Language::ZhCn, Language::En];
+
+// 每种语言的文本表文件
+fn locale_path(language: Language) -> &'static str {
+    match language {
+        Language::ZhCn => "assets/locales/zh-CN.ron",
+        Language::En => "assets/locales/en.ron",
+    }
+}
+
+// 每种语言使用的字体，FiraSans 没有中文字形
+pub fn language_font(language: Language, assets: &GameAssets) -> Handle<Font> {
+    match language {
+        Language::ZhCn => assets.font_ywgh.clone(),
+        Language::En => assets.font.clone(),
+    }
+}
+
+// 本地化文本表，当前语言找不到的键先用中文，还找不到就原样显示，方便发现漏翻的文本
+#[derive(Resource, Default, Debug)]
+pub struct Texts {
+    language: Language,
+    tables: HashMap<Language, HashMap<String, String>>,
+}
+
+impl Texts {
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        [self.language, Language::default()]
+            .iter()
+            .find_map(|language| self.tables.get(language)?.get(key))
+            .map_or(key, String::as_str)
+    }
+
+    // 把文本里的 {name} 替换成对应的参数
+    pub fn format(&self, key: &str, args: &[(&str, &dyn std::fmt::Display)]) -> String {
+        args.iter()
+            .fold(self.get(key).to_string(), |text, (name, value)| {
+                text.replace(&format!("{{{}}}", name), &value.to_string())
+            })
+    }
+
+    pub fn font(&self, assets: &GameAssets) -> Handle<Font> {
+        language_font(self.language, assets)
+    }
+}
+
+// 文本内容由键决定，切换语言时自动更新文本和字体
+#[derive(Component, Debug, Clone)]
+pub struct LocalizedText(pub String);
+
+impl LocalizedText {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(key.into())
+    }
+}
+
+// 内容由系统生成的文本，只跟着语言切换字体
+#[derive(Component, Debug, Clone, Copy)]
+pub struct LocalizedFont;
+
+fn load_texts(mut commands: Commands, language: Res<Language>) {
+    let tables = LANGUAGES
+        .into_iter()
+        .map(|language| {
+            let path = locale_path(language);
+            let file = File::open(path).unwrap_or_else(|_| panic!("Failed to open {}", path));
+            let table: HashMap<String, String> =
+                from_reader(file).unwrap_or_else(|_| panic!("Unable to load {}", path));
+            (language, table)
+        })
+        .collect();
+    commands.insert_resource(Texts {
+        language: *language,
+        tables,
+    });
+}
+
+fn switch_language(language: Res<Language>, mut texts: ResMut<Texts>) {
+    if texts.language != *language {
+        texts.language = *language;
+    }
+}
+
+fn update_localized_text(
+    texts: Res<Texts>,
+    assets: Option<Res<GameAssets>>,
+    mut q_text: Query<(
+        Ref<LocalizedText>,
+        Option<&mut Text>,
+        Option<&mut Text2d>,
+        &mut TextFont,
+    )>,
+) {
+    for (localized, text, text_2d, mut font) in q_text.iter_mut() {
+        if !texts.is_changed() && !localized.is_changed() {
+            continue;
+        }
+        let content = texts.get(&localized.0).to_string();
+        if let Some(mut text) = text {
+            text.0 = content;
+        } else if let Some(mut text_2d) = text_2d {
+            text_2d.0 = content;
+        }
+        if let Some(assets) = assets.as_deref() {
+            font.font = texts.font(assets);
+        }
+    }
+}
+
+fn update_localized_font(
+    texts: Res<Texts>,
+    assets: Option<Res<GameAssets>>,
+    mut q_font: Query<(Ref<LocalizedFont>, &mut TextFont)>,
+) {
+    let Some(assets) = assets else {
+        return;
+    };
+    for (localized, mut font) in q_font.iter_mut() {
+        if texts.is_changed() || localized.is_added() {
+            font.font = texts.font(&assets);
+        }
+    }
+}
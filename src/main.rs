@@ -1,12 +1,23 @@
+// Bevy 的系统通过参数声明需要的数据，参数多、查询类型复杂是常态
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
 mod assets;
 mod audio;
 mod ball;
 mod brick;
 mod collision_group;
+#[cfg(feature = "dev-tools")]
+mod dev_tools;
+mod difficulty;
+mod effects;
 mod enemy;
 mod events;
 mod item;
+mod localization;
 mod menu;
+mod progression;
+mod run_stats;
+mod settings;
 mod ui;
 mod utils;
 mod world;
@@ -22,6 +33,7 @@ use bevy_asset_loader::loading_state::{LoadingState, LoadingStateAppExt};
 use bevy_framepace::{self, FramepacePlugin, FramepaceSettings, Limiter};
 use bevy_rapier2d::prelude::*;
 
+// 游戏的虚拟分辨率，也是窗口的初始大小；实际窗口大小由摄像机缩放适配
 pub const WINDOW_WIDTH: f32 = 1280.0;
 pub const WINDOW_HEIGHT: f32 = 720.0;
 
@@ -37,53 +49,68 @@ pub enum GameState {
 }
 
 fn main() {
-    App::new()
-        .add_plugins(
-            DefaultPlugins
-                .set(ImagePlugin::default_nearest())
-                .set(WindowPlugin {
-                    primary_window: Some(Window {
-                        resolution: (WINDOW_WIDTH, WINDOW_HEIGHT).into(),
-                        present_mode: PresentMode::Fifo,
-                        mode: WindowMode::Windowed,
-                        fit_canvas_to_parent: false,
-                        canvas: Some("#game-canvas".to_string()),
-                        cursor_options: CursorOptions {
-                            visible: true,
-                            ..default()
-                        },
+    let mut app = App::new();
+    app.add_plugins(
+        DefaultPlugins
+            .set(ImagePlugin::default_nearest())
+            .set(WindowPlugin {
+                primary_window: Some(Window {
+                    resolution: (WINDOW_WIDTH, WINDOW_HEIGHT).into(),
+                    present_mode: PresentMode::Fifo,
+                    mode: WindowMode::Windowed,
+                    fit_canvas_to_parent: true,
+                    resizable: true,
+                    canvas: Some("#game-canvas".to_string()),
+                    cursor_options: CursorOptions {
+                        visible: true,
                         ..default()
-                    }),
-                    ..default()
-                })
-                .set(AssetPlugin {
-                    meta_check: AssetMetaCheck::Never,
+                    },
                     ..default()
                 }),
-        )
-        .init_state::<GameState>()
-        .add_loading_state(
-            LoadingState::new(GameState::AssetLoading)
-                .continue_to_state(GameState::Splash)
-                .load_collection::<GameAssets>(),
-        )
-        .add_plugins(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0))
-        .add_plugins(RapierDebugRenderPlugin::default())
-        .add_plugins(FramepacePlugin)
-        .insert_resource(FramepaceSettings {
-            limiter: Limiter::from_framerate(60.0),
-        })
-        .add_plugins((
-            ui::UiPlugin,
-            world::WorldPlugin,
-            brick::BrickPlugin,
-            events::EventPlugin,
-            enemy::EnemyPlugin,
-            item::ItemPlugin,
-            ball::BallPlugin,
-            utils::UtilsPlugin,
-            menu::MenuPlugin,
-            audio::GameAudioPlugin,
-        ))
-        .run();
+                ..default()
+            })
+            .set(AssetPlugin {
+                meta_check: AssetMetaCheck::Never,
+                ..default()
+            }),
+    )
+    .init_state::<GameState>()
+    .add_loading_state(
+        LoadingState::new(GameState::AssetLoading)
+            .continue_to_state(GameState::Splash)
+            .load_collection::<GameAssets>(),
+    )
+    // 物理在固定时间步里运行，和帧率无关
+    .insert_resource(Time::<Fixed>::from_hz(60.0))
+    .insert_resource(TimestepMode::Fixed {
+        dt: 1.0 / 60.0,
+        substeps: 1,
+    })
+    .add_plugins(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0).in_fixed_schedule())
+    .add_plugins(FramepacePlugin)
+    .insert_resource(FramepaceSettings {
+        limiter: Limiter::from_framerate(60.0),
+    })
+    .add_plugins((
+        ui::UiPlugin,
+        world::WorldPlugin,
+        brick::BrickPlugin,
+        events::EventPlugin,
+        enemy::EnemyPlugin,
+        item::ItemPlugin,
+        ball::BallPlugin,
+        utils::UtilsPlugin,
+        menu::MenuPlugin,
+        audio::GameAudioPlugin,
+        effects::EffectsPlugin,
+        settings::SettingsPlugin,
+        localization::LocalizationPlugin,
+        run_stats::RunStatsPlugin,
+        // 元组最多 15 个插件，局外的设置放在一组
+        (progression::ProgressionPlugin, difficulty::DifficultyPlugin),
+    ));
+    // 控制台、作弊命令和物理调试渲染，发布版本不启用
+    #[cfg(feature = "dev-tools")]
+    app.add_plugins(dev_tools::DevToolsPlugin);
+    app.run();
 }
@@ -1,23 +1,36 @@
+mod character_select;
+mod collection;
+mod difficulty;
 mod init;
 mod splash;
+mod summary;
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use crate::audio::Volume;
+use crate::audio::{AudioBus, AudioMixer};
+use crate::settings::Language;
 
 pub struct MenuPlugin;
 
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((init::InitPlugin, splash::SplashPlugin))
-            .insert_resource(DisplayQuality::Medium);
+        app.add_plugins((
+            init::InitPlugin,
+            character_select::CharacterSelectPlugin,
+            collection::CollectionPlugin,
+            difficulty::DifficultyMenuPlugin,
+            splash::SplashPlugin,
+            summary::SummaryPlugin,
+        ));
     }
 }
 
 const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
 
-// One of the two settings that can be set through the menu. It will be a resource in the app
-#[derive(Resource, Debug, Component, PartialEq, Eq, Clone, Copy)]
+// One of the settings that can be set through the menu. It will be a resource in the app,
+// loaded and saved by `settings::SettingsPlugin`
+#[derive(Resource, Debug, Component, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum DisplayQuality {
     Low,
     Medium,
@@ -0,0 +1,388 @@
+use bevy::{color::palettes::css::CRIMSON, prelude::*};
+
+use crate::{
+    brick::{
+        character::{Character, CharacterRegistry, SelectedCharacters},
+        player::{PlayMode, Player},
+    },
+    localization::{LocalizedFont, LocalizedText, Texts},
+    progression::{Progress, Progression, Unlockable},
+    GameAssets,
+};
+
+use super::{
+    despawn_screen,
+    init::{MenuButtonAction, MenuState, SelectedOption, NORMAL_BUTTON},
+    TEXT_COLOR,
+};
+
+const LOCKED_COLOR: Color = Color::srgb(0.55, 0.55, 0.55);
+// 角色图按原图的 1.5 倍显示
+const PREVIEW_SCALE: f32 = 1.5;
+
+pub struct CharacterSelectPlugin;
+
+// 开始新游戏之前选择角色
+impl Plugin for CharacterSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ChoosingFor>()
+            .add_systems(
+                OnEnter(MenuState::CharacterSelect),
+                (sync_player_one, character_select_setup).chain(),
+            )
+            .add_systems(
+                Update,
+                (
+                    (character_button, play_mode_button, choosing_for_button),
+                    sync_player_one,
+                    // 1P 选中的角色保存在进度里，任何选择变化后重新生成界面
+                    (
+                        despawn_screen::<OnCharacterSelectScreen>,
+                        character_select_setup,
+                    )
+                        .chain()
+                        .run_if(
+                            resource_changed::<Progress>
+                                .or(resource_changed::<SelectedCharacters>)
+                                .or(resource_changed::<PlayMode>)
+                                .or(resource_changed::<ChoosingFor>),
+                        ),
+                )
+                    .chain()
+                    .run_if(in_state(MenuState::CharacterSelect)),
+            )
+            .add_systems(
+                OnExit(MenuState::CharacterSelect),
+                despawn_screen::<OnCharacterSelectScreen>,
+            );
+    }
+}
+
+#[derive(Component)]
+struct OnCharacterSelectScreen;
+
+#[derive(Component)]
+struct CharacterButton(Character);
+
+// 切换单人和双人
+#[derive(Component)]
+struct PlayModeButton;
+
+// 双人时切换正在给哪个玩家选角色
+#[derive(Component)]
+struct ChoosingForButton(Player);
+
+#[derive(Resource, Default, PartialEq)]
+struct ChoosingFor(Player);
+
+// 1P 的角色跟随进度里保存的角色
+fn sync_player_one(
+    progress: Res<Progress>,
+    progression: Res<Progression>,
+    characters: Res<CharacterRegistry>,
+    mut selected: ResMut<SelectedCharacters>,
+) {
+    let character = progress.current_character(&progression, &characters);
+    if selected.get(Player::One) != character {
+        selected.set(Player::One, character);
+    }
+}
+
+fn character_select_setup(
+    mut commands: Commands,
+    progress: Res<Progress>,
+    progression: Res<Progression>,
+    characters: Res<CharacterRegistry>,
+    selected: Res<SelectedCharacters>,
+    play_mode: Res<PlayMode>,
+    choosing_for: Res<ChoosingFor>,
+    texts: Res<Texts>,
+    game_assets: Res<GameAssets>,
+) {
+    let players = play_mode.players();
+
+    let font = texts.font(&game_assets);
+    let text_style = |font_size: f32, color: Color| {
+        (
+            TextFont {
+                font: font.clone(),
+                font_size,
+                ..default()
+            },
+            TextColor(color),
+            LocalizedFont,
+        )
+    };
+    let button_node = Node {
+        width: Val::Px(200.0),
+        height: Val::Px(65.0),
+        margin: UiRect::all(Val::Px(20.0)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            OnCharacterSelectScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::all(Val::Px(16.0)),
+                        row_gap: Val::Px(8.0),
+                        ..default()
+                    },
+                    BackgroundColor(CRIMSON.into()),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::default(),
+                        LocalizedText::new("menu.choose_character"),
+                        text_style(33.0, TEXT_COLOR),
+                    ));
+
+                    parent
+                        .spawn(Node {
+                            align_items: AlignItems::Center,
+                            column_gap: Val::Px(10.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            let mode = match *play_mode {
+                                PlayMode::Solo => "menu.players.solo",
+                                PlayMode::Coop => "menu.players.coop",
+                            };
+                            parent
+                                .spawn((
+                                    Button,
+                                    button_node.clone(),
+                                    BackgroundColor(NORMAL_BUTTON),
+                                    PlayModeButton,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn((
+                                        Text::default(),
+                                        LocalizedText::new(mode),
+                                        text_style(24.0, TEXT_COLOR),
+                                    ));
+                                });
+                            if *play_mode == PlayMode::Solo {
+                                return;
+                            }
+                            parent.spawn((
+                                Text::default(),
+                                LocalizedText::new("menu.choosing_for"),
+                                text_style(24.0, TEXT_COLOR),
+                            ));
+                            for &player in players {
+                                let mut entity = parent.spawn((
+                                    Button,
+                                    Node {
+                                        width: Val::Px(65.0),
+                                        height: Val::Px(45.0),
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..default()
+                                    },
+                                    BackgroundColor(NORMAL_BUTTON),
+                                    ChoosingForButton(player),
+                                ));
+                                if choosing_for.0 == player {
+                                    entity.insert(SelectedOption);
+                                }
+                                entity.with_children(|parent| {
+                                    parent.spawn((
+                                        Text::default(),
+                                        LocalizedText::new(player.key()),
+                                        text_style(24.0, player.color()),
+                                    ));
+                                });
+                            }
+                        });
+
+                    parent
+                        .spawn(Node {
+                            column_gap: Val::Px(10.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            for (character, def) in characters.iter() {
+                                let content = Unlockable::Character(def.id.clone());
+                                let unlocked = progress.has(&progression, &content);
+                                let color = if unlocked { TEXT_COLOR } else { LOCKED_COLOR };
+                                let details = if unlocked {
+                                    vec![
+                                        texts.get(&def.description).to_string(),
+                                        texts.format(
+                                            "character.stats",
+                                            &[
+                                                ("speed", &def.speed),
+                                                ("pressure", &def.max_pressure),
+                                                ("balls", &def.inventory.balls.len()),
+                                                ("capacity", &def.inventory.capacity),
+                                            ],
+                                        ),
+                                    ]
+                                } else {
+                                    progression
+                                        .unlocks
+                                        .iter()
+                                        .find(|unlock| unlock.content == content)
+                                        .map(|unlock| unlock.condition.describe(&texts))
+                                        .into_iter()
+                                        .collect()
+                                };
+
+                                let mut entity = parent.spawn((
+                                    Node {
+                                        width: Val::Px(260.0),
+                                        min_height: Val::Px(180.0),
+                                        flex_direction: FlexDirection::Column,
+                                        align_items: AlignItems::Center,
+                                        row_gap: Val::Px(6.0),
+                                        padding: UiRect::all(Val::Px(8.0)),
+                                        ..default()
+                                    },
+                                    BackgroundColor(NORMAL_BUTTON),
+                                ));
+                                if unlocked {
+                                    entity.insert((Button, CharacterButton(character)));
+                                }
+                                if selected.get(choosing_for.0) == character {
+                                    entity.insert(SelectedOption);
+                                }
+                                entity.with_children(|parent| {
+                                    // 双人时标出每个玩家选的角色
+                                    if *play_mode == PlayMode::Coop {
+                                        parent.spawn(Node::default()).with_children(|parent| {
+                                            for &player in players {
+                                                if selected.get(player) != character {
+                                                    continue;
+                                                }
+                                                parent.spawn((
+                                                    Text::default(),
+                                                    LocalizedText::new(player.key()),
+                                                    text_style(20.0, player.color()),
+                                                    Node {
+                                                        margin: UiRect::horizontal(Val::Px(4.0)),
+                                                        ..default()
+                                                    },
+                                                ));
+                                            }
+                                        });
+                                    }
+                                    let (width, height) = def.sprite.tile_size;
+                                    let mut image = characters.image_node(character);
+                                    if !unlocked {
+                                        image.color = image.color.mix(&Color::BLACK, 0.6);
+                                    }
+                                    parent.spawn((
+                                        image,
+                                        Node {
+                                            width: Val::Px(width as f32 * PREVIEW_SCALE),
+                                            height: Val::Px(height as f32 * PREVIEW_SCALE),
+                                            ..default()
+                                        },
+                                    ));
+                                    parent.spawn((
+                                        Text::new(texts.get(&def.name)),
+                                        text_style(24.0, color),
+                                    ));
+                                    for detail in details {
+                                        parent.spawn((
+                                            Text::new(detail),
+                                            text_style(16.0, color),
+                                            TextLayout::new_with_justify(JustifyText::Center),
+                                        ));
+                                    }
+                                });
+                            }
+                        });
+
+                    parent.spawn(Node::default()).with_children(|parent| {
+                        for (action, text) in [
+                            (MenuButtonAction::BackToMainMenu, "menu.back"),
+                            (MenuButtonAction::Play, "menu.start"),
+                        ] {
+                            parent
+                                .spawn((
+                                    Button,
+                                    button_node.clone(),
+                                    BackgroundColor(NORMAL_BUTTON),
+                                    action,
+                                ))
+                                .with_children(|parent| {
+                                    parent.spawn((
+                                        Text::default(),
+                                        LocalizedText::new(text),
+                                        text_style(33.0, TEXT_COLOR),
+                                    ));
+                                });
+                        }
+                    });
+                });
+        });
+}
+
+// 1P 的选择写进进度，2P 的只在本次生效
+fn character_button(
+    interaction_query: Query<(&Interaction, &CharacterButton), Changed<Interaction>>,
+    choosing_for: Res<ChoosingFor>,
+    mut progress: ResMut<Progress>,
+    mut selected: ResMut<SelectedCharacters>,
+    characters: Res<CharacterRegistry>,
+) {
+    for (interaction, &CharacterButton(character)) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match choosing_for.0 {
+            Player::One => {
+                let id = &characters.get(character).id;
+                if progress.character != *id {
+                    progress.character = id.clone();
+                }
+            }
+            Player::Two => {
+                if selected.get(Player::Two) != character {
+                    selected.set(Player::Two, character);
+                }
+            }
+        }
+    }
+}
+
+fn play_mode_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<PlayModeButton>)>,
+    mut play_mode: ResMut<PlayMode>,
+    mut choosing_for: ResMut<ChoosingFor>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            *play_mode = play_mode.toggle();
+            choosing_for.set_if_neq(ChoosingFor(Player::One));
+        }
+    }
+}
+
+fn choosing_for_button(
+    interaction_query: Query<(&Interaction, &ChoosingForButton), Changed<Interaction>>,
+    mut choosing_for: ResMut<ChoosingFor>,
+) {
+    for (interaction, &ChoosingForButton(player)) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            choosing_for.set_if_neq(ChoosingFor(player));
+        }
+    }
+}
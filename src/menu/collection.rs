@@ -0,0 +1,328 @@
+use bevy::{color::palettes::css::CRIMSON, prelude::*};
+
+use crate::{
+    brick::character::CharacterRegistry,
+    item::ItemRegistry,
+    localization::{LocalizedFont, LocalizedText, Texts},
+    progression::{Progress, Progression, UnlockCondition, Unlockable},
+    GameAssets,
+};
+
+use super::{
+    despawn_screen,
+    init::{MenuButtonAction, MenuState, SelectedOption, NORMAL_BUTTON},
+    TEXT_COLOR,
+};
+
+const ICON_SIZE: f32 = 32.0;
+const LOCKED_COLOR: Color = Color::srgb(0.55, 0.55, 0.55);
+
+pub struct CollectionPlugin;
+
+// 收藏界面：查看解锁进度、购买解锁内容和选择初始配置
+impl Plugin for CollectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuState::Collection), collection_setup)
+            .add_systems(
+                Update,
+                (
+                    collection_action,
+                    // 进度变化后重新生成界面
+                    (despawn_screen::<OnCollectionScreen>, collection_setup)
+                        .chain()
+                        .run_if(resource_changed::<Progress>),
+                )
+                    .chain()
+                    .run_if(in_state(MenuState::Collection)),
+            )
+            .add_systems(
+                OnExit(MenuState::Collection),
+                despawn_screen::<OnCollectionScreen>,
+            );
+    }
+}
+
+#[derive(Component)]
+struct OnCollectionScreen;
+
+#[derive(Component)]
+enum CollectionButton {
+    // UnlockDef 在 progression.ron 里的序号
+    Buy(usize),
+    SelectLoadout(String),
+}
+
+fn collection_setup(
+    mut commands: Commands,
+    progress: Res<Progress>,
+    progression: Res<Progression>,
+    registry: Res<ItemRegistry>,
+    characters: Res<CharacterRegistry>,
+    texts: Res<Texts>,
+    game_assets: Res<GameAssets>,
+) {
+    let font = texts.font(&game_assets);
+    let text_style = |font_size: f32, color: Color| {
+        (
+            TextFont {
+                font: font.clone(),
+                font_size,
+                ..default()
+            },
+            TextColor(color),
+            LocalizedFont,
+        )
+    };
+    let current_loadout = progress
+        .current_loadout(&progression)
+        .map(|loadout| loadout.id.clone());
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            OnCollectionScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::all(Val::Px(16.0)),
+                        row_gap: Val::Px(8.0),
+                        ..default()
+                    },
+                    BackgroundColor(CRIMSON.into()),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::default(),
+                        LocalizedText::new("menu.collection"),
+                        text_style(33.0, TEXT_COLOR),
+                    ));
+                    parent.spawn((
+                        Text::new(
+                            texts.format("collection.currency", &[("count", &progress.currency)]),
+                        ),
+                        text_style(24.0, TEXT_COLOR),
+                    ));
+
+                    // 初始配置，已解锁的可以点击选中
+                    parent.spawn((
+                        Text::default(),
+                        LocalizedText::new("collection.loadouts"),
+                        text_style(24.0, TEXT_COLOR),
+                    ));
+                    parent
+                        .spawn(Node {
+                            column_gap: Val::Px(10.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            for loadout in progression.loadouts.iter() {
+                                let content = Unlockable::Loadout(loadout.id.clone());
+                                let unlocked = progress.has(&progression, &content);
+                                let detail = if unlocked {
+                                    texts.get(&loadout.description).to_string()
+                                } else {
+                                    progression
+                                        .unlocks
+                                        .iter()
+                                        .find(|unlock| unlock.content == content)
+                                        .map(|unlock| unlock.condition.describe(&texts))
+                                        .unwrap_or_default()
+                                };
+                                let mut entity = parent.spawn((
+                                    Node {
+                                        width: Val::Px(240.0),
+                                        min_height: Val::Px(80.0),
+                                        flex_direction: FlexDirection::Column,
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        padding: UiRect::all(Val::Px(6.0)),
+                                        ..default()
+                                    },
+                                    BackgroundColor(NORMAL_BUTTON),
+                                ));
+                                if unlocked {
+                                    entity.insert((
+                                        Button,
+                                        CollectionButton::SelectLoadout(loadout.id.clone()),
+                                    ));
+                                }
+                                if current_loadout.as_ref() == Some(&loadout.id) {
+                                    entity.insert(SelectedOption);
+                                }
+                                let color = if unlocked { TEXT_COLOR } else { LOCKED_COLOR };
+                                entity.with_children(|parent| {
+                                    parent.spawn((
+                                        Text::new(texts.get(&loadout.name)),
+                                        text_style(22.0, color),
+                                    ));
+                                    parent.spawn((
+                                        Text::new(detail),
+                                        text_style(16.0, color),
+                                        TextLayout::new_with_justify(JustifyText::Center),
+                                    ));
+                                });
+                            }
+                        });
+
+                    // 所有解锁项和它们的条件
+                    parent.spawn((
+                        Text::default(),
+                        LocalizedText::new("collection.unlocks"),
+                        text_style(24.0, TEXT_COLOR),
+                    ));
+                    for (index, unlock) in progression.unlocks.iter().enumerate() {
+                        let unlocked = progress.is_unlocked(unlock);
+                        let color = if unlocked { TEXT_COLOR } else { LOCKED_COLOR };
+                        let (name, icon) = match &unlock.content {
+                            Unlockable::Item(id) => match registry.find(id) {
+                                Some(item) => (
+                                    registry.get(item).name.as_str(),
+                                    Some((registry.icon(item), registry.get(item).tint_color())),
+                                ),
+                                None => (id.as_str(), None),
+                            },
+                            Unlockable::Ball(ball) => (ball.key(), None),
+                            Unlockable::Loadout(id) => (
+                                progression
+                                    .loadout(id)
+                                    .map_or(id.as_str(), |loadout| loadout.name.as_str()),
+                                None,
+                            ),
+                            Unlockable::Character(id) => (
+                                characters.find(id).map_or(id.as_str(), |character| {
+                                    characters.get(character).name.as_str()
+                                }),
+                                None,
+                            ),
+                        };
+                        let status = if unlocked {
+                            texts.get("collection.unlocked").to_string()
+                        } else {
+                            unlock.condition.describe(&texts)
+                        };
+                        parent
+                            .spawn(Node {
+                                width: Val::Px(600.0),
+                                align_items: AlignItems::Center,
+                                column_gap: Val::Px(10.0),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                let icon_node = Node {
+                                    width: Val::Px(ICON_SIZE),
+                                    height: Val::Px(ICON_SIZE),
+                                    ..default()
+                                };
+                                match icon {
+                                    // 没解锁的道具图标变暗
+                                    Some((image, tint)) => {
+                                        let tint = if unlocked {
+                                            tint
+                                        } else {
+                                            tint.mix(&Color::BLACK, 0.6)
+                                        };
+                                        parent.spawn((
+                                            ImageNode::new(image).with_color(tint),
+                                            icon_node,
+                                        ));
+                                    }
+                                    None => {
+                                        parent.spawn(icon_node);
+                                    }
+                                }
+                                parent.spawn((
+                                    Text::new(texts.get(name)),
+                                    text_style(22.0, color),
+                                    Node {
+                                        flex_grow: 1.0,
+                                        ..default()
+                                    },
+                                ));
+                                parent.spawn((Text::new(status), text_style(18.0, color)));
+                                if let (false, UnlockCondition::Cost(cost)) =
+                                    (unlocked, unlock.condition)
+                                {
+                                    if progress.currency >= cost {
+                                        parent
+                                            .spawn((
+                                                Button,
+                                                Node {
+                                                    padding: UiRect::axes(
+                                                        Val::Px(10.0),
+                                                        Val::Px(4.0),
+                                                    ),
+                                                    ..default()
+                                                },
+                                                BackgroundColor(NORMAL_BUTTON),
+                                                CollectionButton::Buy(index),
+                                            ))
+                                            .with_children(|parent| {
+                                                parent.spawn((
+                                                    Text::default(),
+                                                    LocalizedText::new("collection.buy"),
+                                                    text_style(18.0, TEXT_COLOR),
+                                                ));
+                                            });
+                                    }
+                                }
+                            });
+                    }
+
+                    parent
+                        .spawn((
+                            Button,
+                            Node {
+                                width: Val::Px(200.0),
+                                height: Val::Px(50.0),
+                                margin: UiRect::top(Val::Px(10.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor(NORMAL_BUTTON),
+                            MenuButtonAction::BackToMainMenu,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::default(),
+                                LocalizedText::new("menu.back"),
+                                text_style(28.0, TEXT_COLOR),
+                            ));
+                        });
+                });
+        });
+}
+
+fn collection_action(
+    interaction_query: Query<(&Interaction, &CollectionButton), Changed<Interaction>>,
+    mut progress: ResMut<Progress>,
+    progression: Res<Progression>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            CollectionButton::Buy(index) => {
+                if let Some(unlock) = progression.unlocks.get(*index) {
+                    progress.buy(unlock);
+                }
+            }
+            CollectionButton::SelectLoadout(id) => {
+                if progress.loadout != *id {
+                    progress.loadout = id.clone();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use bevy::{color::palettes::css::CRIMSON, prelude::*};
+
+use crate::{
+    difficulty::{CustomDifficulty, Difficulty, DifficultyConfig, DifficultyModifiers, Modifier},
+    localization::{LocalizedFont, LocalizedText, Texts},
+    GameAssets,
+};
+
+use super::{
+    despawn_screen,
+    init::{MenuButtonAction, MenuState, SelectedOption, NORMAL_BUTTON},
+    TEXT_COLOR,
+};
+
+// 自定义倍率每次调整的幅度和范围
+const MODIFIER_STEP: f32 = 0.1;
+const MODIFIER_MIN: f32 = 0.1;
+const MODIFIER_MAX: f32 = 3.0;
+
+pub struct DifficultyMenuPlugin;
+
+// 设置里的难度界面，选择预设难度或者调整自定义倍率
+impl Plugin for DifficultyMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(MenuState::SettingsDifficulty),
+            difficulty_menu_setup,
+        )
+        .add_systems(
+            Update,
+            (
+                (difficulty_button, modifier_button),
+                // 难度或倍率变化后重新生成界面
+                (
+                    despawn_screen::<OnDifficultyMenuScreen>,
+                    difficulty_menu_setup,
+                )
+                    .chain()
+                    .run_if(
+                        resource_changed::<Difficulty>.or(resource_changed::<CustomDifficulty>),
+                    ),
+            )
+                .chain()
+                .run_if(in_state(MenuState::SettingsDifficulty)),
+        )
+        .add_systems(
+            OnExit(MenuState::SettingsDifficulty),
+            despawn_screen::<OnDifficultyMenuScreen>,
+        );
+    }
+}
+
+#[derive(Component)]
+struct OnDifficultyMenuScreen;
+
+// 调整一项自定义倍率，step 为正表示增加
+#[derive(Component)]
+struct ModifierButton {
+    modifier: Modifier,
+    step: f32,
+}
+
+fn difficulty_menu_setup(
+    mut commands: Commands,
+    difficulty: Res<Difficulty>,
+    custom: Res<CustomDifficulty>,
+    config: Res<DifficultyConfig>,
+    texts: Res<Texts>,
+    game_assets: Res<GameAssets>,
+) {
+    let font = texts.font(&game_assets);
+    let text_style = |font_size: f32| {
+        (
+            TextFont {
+                font: font.clone(),
+                font_size,
+                ..default()
+            },
+            TextColor(TEXT_COLOR),
+            LocalizedFont,
+        )
+    };
+    let small_button = Node {
+        width: Val::Px(50.0),
+        height: Val::Px(40.0),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let modifiers = config.modifiers(*difficulty, &custom);
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            OnDifficultyMenuScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::all(Val::Px(16.0)),
+                        row_gap: Val::Px(8.0),
+                        ..default()
+                    },
+                    BackgroundColor(CRIMSON.into()),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::default(),
+                        LocalizedText::new("menu.difficulty"),
+                        text_style(33.0),
+                    ));
+
+                    parent
+                        .spawn(Node {
+                            column_gap: Val::Px(10.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            for setting in Difficulty::ALL {
+                                let mut entity = parent.spawn((
+                                    Button,
+                                    Node {
+                                        width: Val::Px(150.0),
+                                        height: Val::Px(55.0),
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..default()
+                                    },
+                                    BackgroundColor(NORMAL_BUTTON),
+                                    setting,
+                                ));
+                                if *difficulty == setting {
+                                    entity.insert(SelectedOption);
+                                }
+                                entity.with_children(|parent| {
+                                    parent.spawn((
+                                        Text::default(),
+                                        LocalizedText::new(setting.key()),
+                                        text_style(26.0),
+                                    ));
+                                });
+                            }
+                        });
+
+                    // 每项倍率一行，调整任何一项都会切换到自定义难度
+                    for (modifier, key) in DifficultyModifiers::FIELDS {
+                        parent
+                            .spawn(Node {
+                                width: Val::Px(520.0),
+                                align_items: AlignItems::Center,
+                                column_gap: Val::Px(10.0),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Text::default(),
+                                    LocalizedText::new(key),
+                                    text_style(22.0),
+                                    Node {
+                                        flex_grow: 1.0,
+                                        ..default()
+                                    },
+                                ));
+                                for (label, step) in [("-", -MODIFIER_STEP), ("+", MODIFIER_STEP)] {
+                                    if step > 0.0 {
+                                        parent.spawn((
+                                            Text::new(format!("x{:.1}", modifiers.get(modifier))),
+                                            text_style(22.0),
+                                            Node {
+                                                width: Val::Px(60.0),
+                                                ..default()
+                                            },
+                                            TextLayout::new_with_justify(JustifyText::Center),
+                                        ));
+                                    }
+                                    parent
+                                        .spawn((
+                                            Button,
+                                            small_button.clone(),
+                                            BackgroundColor(NORMAL_BUTTON),
+                                            ModifierButton { modifier, step },
+                                        ))
+                                        .with_children(|parent| {
+                                            parent.spawn((Text::new(label), text_style(26.0)));
+                                        });
+                                }
+                            });
+                    }
+
+                    parent.spawn((
+                        Text::default(),
+                        LocalizedText::new("difficulty.depth_hint"),
+                        text_style(18.0),
+                    ));
+
+                    parent
+                        .spawn((
+                            Button,
+                            Node {
+                                width: Val::Px(200.0),
+                                height: Val::Px(65.0),
+                                margin: UiRect::all(Val::Px(20.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor(NORMAL_BUTTON),
+                            MenuButtonAction::BackToSettings,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::default(),
+                                LocalizedText::new("menu.back"),
+                                text_style(33.0),
+                            ));
+                        });
+                });
+        });
+}
+
+fn difficulty_button(
+    interaction_query: Query<(&Interaction, &Difficulty), (Changed<Interaction>, With<Button>)>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    for (interaction, setting) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            difficulty.set_if_neq(*setting);
+        }
+    }
+}
+
+// 从预设难度开始调整时，先把预设的倍率复制到自定义难度里
+fn modifier_button(
+    interaction_query: Query<(&Interaction, &ModifierButton), Changed<Interaction>>,
+    mut difficulty: ResMut<Difficulty>,
+    mut custom: ResMut<CustomDifficulty>,
+    config: Res<DifficultyConfig>,
+) {
+    for (interaction, button) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if *difficulty != Difficulty::Custom {
+            custom.0 = config.modifiers(*difficulty, &custom);
+            *difficulty = Difficulty::Custom;
+        }
+        let value = custom.0.get_mut(button.modifier);
+        // 四舍五入到一位小数，避免浮点误差累积
+        *value = ((*value + button.step) * 10.0).round() / 10.0;
+        *value = value.clamp(MODIFIER_MIN, MODIFIER_MAX);
+    }
+}
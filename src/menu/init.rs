@@ -1,8 +1,14 @@
 use bevy::{app::AppExit, color::palettes::css::CRIMSON, input::gamepad, prelude::*};
 
-use crate::{GameAssets, GameState};
+use crate::{
+    audio::PlaySound,
+    item::ItemRegistry,
+    localization::{language_font, LocalizedFont, LocalizedText, Texts},
+    run_stats::LifetimeStats,
+    GameAssets, GameState,
+};
 
-use super::{despawn_screen, DisplayQuality, Volume, TEXT_COLOR};
+use super::{despawn_screen, AudioBus, AudioMixer, DisplayQuality, Language, TEXT_COLOR};
 
 pub struct InitPlugin;
 
@@ -30,7 +36,8 @@ impl Plugin for InitPlugin {
             )
             .add_systems(
                 Update,
-                (setting_button::<DisplayQuality>.run_if(in_state(MenuState::SettingsDisplay)),),
+                (setting_button::<DisplayQuality>, setting_button::<Language>)
+                    .run_if(in_state(MenuState::SettingsDisplay)),
             )
             .add_systems(
                 OnExit(MenuState::SettingsDisplay),
@@ -40,27 +47,42 @@ impl Plugin for InitPlugin {
             .add_systems(OnEnter(MenuState::SettingsSound), sound_settings_menu_setup)
             .add_systems(
                 Update,
-                setting_button::<Volume>.run_if(in_state(MenuState::SettingsSound)),
+                (volume_button, mute_button).run_if(in_state(MenuState::SettingsSound)),
             )
             .add_systems(
                 OnExit(MenuState::SettingsSound),
                 despawn_screen::<OnSoundSettingsMenuScreen>,
             )
-            // Common systems to all screens that handles buttons behavior
+            // Systems to handle the lifetime statistics screen
+            .add_systems(OnEnter(MenuState::Statistics), statistics_menu_setup)
+            .add_systems(
+                OnExit(MenuState::Statistics),
+                despawn_screen::<OnStatisticsMenuScreen>,
+            )
+            // Common systems to all screens that handles buttons behavior, the end-of-run
+            // summary reuses them for its buttons
             .add_systems(
                 Update,
-                (menu_action, button_system).run_if(in_state(GameState::Menu)),
+                (menu_action, button_system, button_click_sound).run_if(
+                    in_state(GameState::Menu)
+                        .or(in_state(GameState::GameOver))
+                        .or(in_state(GameState::Win)),
+                ),
             );
     }
 }
 
 // State used for the current menu screen
 #[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
-enum MenuState {
+pub(super) enum MenuState {
     Main,
     Settings,
     SettingsDisplay,
     SettingsSound,
+    SettingsDifficulty,
+    Statistics,
+    Collection,
+    CharacterSelect,
     #[default]
     Disabled,
 }
@@ -81,22 +103,43 @@ struct OnDisplaySettingsMenuScreen;
 #[derive(Component)]
 struct OnSoundSettingsMenuScreen;
 
-const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
+// Tag component used to tag entities added on the lifetime statistics screen
+#[derive(Component)]
+struct OnStatisticsMenuScreen;
+
+pub(super) const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
 const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
 const HOVERED_PRESSED_BUTTON: Color = Color::srgb(0.25, 0.65, 0.25);
 const PRESSED_BUTTON: Color = Color::srgb(0.35, 0.75, 0.35);
 
 // Tag component used to mark which setting is currently selected
 #[derive(Component)]
-struct SelectedOption;
+pub(super) struct SelectedOption;
+
+// 音量分为 0~10 档
+const VOLUME_LEVELS: u32 = 10;
+
+#[derive(Component, Clone, Copy, PartialEq)]
+struct VolumeButton {
+    bus: AudioBus,
+    level: u32,
+}
+
+#[derive(Component, Clone, Copy)]
+struct MuteButton(AudioBus);
 
 // All actions that can be triggered from a button click
 #[derive(Component)]
-enum MenuButtonAction {
+pub(super) enum MenuButtonAction {
+    // 主菜单的新游戏按钮，先进入选择角色界面
+    NewGame,
     Play,
     Settings,
+    Statistics,
+    Collection,
     SettingsDisplay,
     SettingsSound,
+    SettingsDifficulty,
     BackToMainMenu,
     BackToSettings,
     Quit,
@@ -119,11 +162,32 @@ fn button_system(
     }
 }
 
+// 按钮的点击声走 UI 总线，由设置里的 UI 音量控制
+fn button_click_sound(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<Button>)>,
+    game_assets: Res<GameAssets>,
+    mut sound_events: EventWriter<PlaySound>,
+) {
+    if interaction_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Pressed)
+    {
+        sound_events.send(PlaySound {
+            clip: game_assets.tennis_bounce_sound.clone(),
+            volume: 0.4,
+            playback_rate: 1.6,
+            bus: AudioBus::Ui,
+            ..default()
+        });
+    }
+}
+
 // This system updates the settings when a new value for a setting is selected, and marks
-// the button as the one currently selected
+// the button as the one currently selected. Several settings can share a screen, so the
+// selected button is looked up among the buttons of the same setting only
 fn setting_button<T: Resource + Component + PartialEq + Copy>(
     interaction_query: Query<(&Interaction, &T, Entity), (Changed<Interaction>, With<Button>)>,
-    selected_query: Single<(Entity, &mut BackgroundColor), With<SelectedOption>>,
+    selected_query: Single<(Entity, &mut BackgroundColor), (With<SelectedOption>, With<T>)>,
     mut commands: Commands,
     mut setting: ResMut<T>,
 ) {
@@ -138,6 +202,50 @@ fn setting_button<T: Resource + Component + PartialEq + Copy>(
     }
 }
 
+// 音量按钮，同一条总线的按钮里只有一个被选中
+fn volume_button(
+    interaction_query: Query<(&Interaction, &VolumeButton, Entity), Changed<Interaction>>,
+    mut q_buttons: Query<
+        (Entity, &VolumeButton, &mut BackgroundColor),
+        (With<SelectedOption>, Without<MuteButton>),
+    >,
+    mut commands: Commands,
+    mut mixer: ResMut<AudioMixer>,
+) {
+    for (interaction, button, entity) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        for (previous, previous_button, mut color) in q_buttons.iter_mut() {
+            if previous_button.bus == button.bus && previous != entity {
+                *color = NORMAL_BUTTON.into();
+                commands.entity(previous).remove::<SelectedOption>();
+            }
+        }
+        commands.entity(entity).insert(SelectedOption);
+        mixer.bus_mut(button.bus).volume = button.level as f32 / VOLUME_LEVELS as f32;
+    }
+}
+
+fn mute_button(
+    interaction_query: Query<(&Interaction, &MuteButton, Entity), Changed<Interaction>>,
+    mut commands: Commands,
+    mut mixer: ResMut<AudioMixer>,
+) {
+    for (interaction, MuteButton(bus), entity) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let bus_settings = mixer.bus_mut(*bus);
+        bus_settings.muted = !bus_settings.muted;
+        if bus_settings.muted {
+            commands.entity(entity).insert(SelectedOption);
+        } else {
+            commands.entity(entity).remove::<SelectedOption>();
+        }
+    }
+}
+
 fn menu_setup(mut menu_state: ResMut<NextState<MenuState>>) {
     menu_state.set(MenuState::Main);
 }
@@ -193,16 +301,18 @@ fn main_menu_setup(mut commands: Commands, game_assets: Res<GameAssets>) {
                     ..default()
                 },))
                 .with_children(|parent| {
-                    // Display three buttons for each action available from the main menu:
+                    // Display a button for each action available from the main menu:
                     // - new game
                     // - settings
+                    // - statistics
+                    // - collection
                     // - quit
                     parent
                         .spawn((
                             Button,
                             button_node.clone(),
                             BackgroundColor(NORMAL_BUTTON),
-                            MenuButtonAction::Play,
+                            MenuButtonAction::NewGame,
                         ))
                         .with_children(|button| {
                             button.spawn((
@@ -210,7 +320,8 @@ fn main_menu_setup(mut commands: Commands, game_assets: Res<GameAssets>) {
                                 button_icon_node.clone(),
                             ));
                             button.spawn((
-                                Text::new("新游戏"),
+                                Text::default(),
+                                LocalizedText::new("menu.new_game"),
                                 button_text_font.clone(),
                                 TextColor(TEXT_COLOR),
                             ));
@@ -228,7 +339,38 @@ fn main_menu_setup(mut commands: Commands, game_assets: Res<GameAssets>) {
                                 button_icon_node.clone(),
                             ));
                             button.spawn((
-                                Text::new("设置"),
+                                Text::default(),
+                                LocalizedText::new("menu.settings"),
+                                button_text_font.clone(),
+                                TextColor(TEXT_COLOR),
+                            ));
+                        });
+                    parent
+                        .spawn((
+                            Button,
+                            button_node.clone(),
+                            BackgroundColor(NORMAL_BUTTON),
+                            MenuButtonAction::Statistics,
+                        ))
+                        .with_children(|button| {
+                            button.spawn((
+                                Text::default(),
+                                LocalizedText::new("menu.statistics"),
+                                button_text_font.clone(),
+                                TextColor(TEXT_COLOR),
+                            ));
+                        });
+                    parent
+                        .spawn((
+                            Button,
+                            button_node.clone(),
+                            BackgroundColor(NORMAL_BUTTON),
+                            MenuButtonAction::Collection,
+                        ))
+                        .with_children(|button| {
+                            button.spawn((
+                                Text::default(),
+                                LocalizedText::new("menu.collection"),
                                 button_text_font.clone(),
                                 TextColor(TEXT_COLOR),
                             ));
@@ -246,7 +388,8 @@ fn main_menu_setup(mut commands: Commands, game_assets: Res<GameAssets>) {
                                 button_icon_node.clone(),
                             ));
                             button.spawn((
-                                Text::new("退出"),
+                                Text::default(),
+                                LocalizedText::new("menu.quit"),
                                 button_text_font.clone(),
                                 TextColor(TEXT_COLOR),
                             ));
@@ -297,9 +440,10 @@ fn settings_menu_setup(mut commands: Commands, game_assets: Res<GameAssets>) {
                 ))
                 .with_children(|parent| {
                     for (action, text) in [
-                        (MenuButtonAction::SettingsDisplay, "画质"),
-                        (MenuButtonAction::SettingsSound, "声音"),
-                        (MenuButtonAction::BackToMainMenu, "返回"),
+                        (MenuButtonAction::SettingsDisplay, "menu.display"),
+                        (MenuButtonAction::SettingsSound, "menu.sound"),
+                        (MenuButtonAction::SettingsDifficulty, "menu.difficulty"),
+                        (MenuButtonAction::BackToMainMenu, "menu.back"),
                     ] {
                         parent
                             .spawn((
@@ -309,7 +453,11 @@ fn settings_menu_setup(mut commands: Commands, game_assets: Res<GameAssets>) {
                                 action,
                             ))
                             .with_children(|parent| {
-                                parent.spawn((Text::new(text), button_text_style.clone()));
+                                parent.spawn((
+                                    Text::default(),
+                                    LocalizedText::new(text),
+                                    button_text_style.clone(),
+                                ));
                             });
                     }
                 });
@@ -319,6 +467,7 @@ fn settings_menu_setup(mut commands: Commands, game_assets: Res<GameAssets>) {
 fn display_settings_menu_setup(
     mut commands: Commands,
     display_quality: Res<DisplayQuality>,
+    language: Res<Language>,
     game_assets: Res<GameAssets>,
 ) {
     fn button_node() -> Node {
@@ -343,6 +492,7 @@ fn display_settings_menu_setup(
     }
 
     let display_quality = *display_quality;
+    let language = *language;
 
     commands
         .spawn((
@@ -378,7 +528,11 @@ fn display_settings_menu_setup(
                         ))
                         .with_children(|parent| {
                             // Display a label for the current setting
-                            parent.spawn((Text::new("品质"), button_text_style(&game_assets)));
+                            parent.spawn((
+                                Text::default(),
+                                LocalizedText::new("menu.quality"),
+                                button_text_style(&game_assets),
+                            ));
 
                             // Display a button for each possible value
                             for quality_setting in [
@@ -398,10 +552,11 @@ fn display_settings_menu_setup(
                                 ));
                                 entity.with_children(|parent| {
                                     parent.spawn((
-                                        Text::new(match quality_setting {
-                                            DisplayQuality::Low => "低",
-                                            DisplayQuality::Medium => "中",
-                                            DisplayQuality::High => "高",
+                                        Text::default(),
+                                        LocalizedText::new(match quality_setting {
+                                            DisplayQuality::Low => "menu.quality.low",
+                                            DisplayQuality::Medium => "menu.quality.medium",
+                                            DisplayQuality::High => "menu.quality.high",
                                         }),
                                         button_text_style(&game_assets),
                                     ));
@@ -411,6 +566,52 @@ fn display_settings_menu_setup(
                                 }
                             }
                         });
+                    parent
+                        .spawn((
+                            Node {
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor(CRIMSON.into()),
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::default(),
+                                LocalizedText::new("menu.language"),
+                                button_text_style(&game_assets),
+                            ));
+
+                            for language_setting in [Language::ZhCn, Language::En] {
+                                let mut entity = parent.spawn((
+                                    Button,
+                                    Node {
+                                        width: Val::Px(150.0),
+                                        height: Val::Px(65.0),
+                                        ..button_node()
+                                    },
+                                    BackgroundColor(NORMAL_BUTTON),
+                                    language_setting,
+                                ));
+                                entity.with_children(|parent| {
+                                    // 语言名称总是用这种语言自己的字体显示
+                                    parent.spawn((
+                                        Text::new(match language_setting {
+                                            Language::ZhCn => "中文",
+                                            Language::En => "English",
+                                        }),
+                                        TextFont {
+                                            font_size: 33.0,
+                                            font: language_font(language_setting, &game_assets),
+                                            ..default()
+                                        },
+                                        TextColor(TEXT_COLOR),
+                                    ));
+                                });
+                                if language == language_setting {
+                                    entity.insert(SelectedOption);
+                                }
+                            }
+                        });
                     // Display the back button to return to the settings screen
                     parent
                         .spawn((
@@ -420,13 +621,21 @@ fn display_settings_menu_setup(
                             MenuButtonAction::BackToSettings,
                         ))
                         .with_children(|parent| {
-                            parent.spawn((Text::new("返回"), button_text_style(&game_assets)));
+                            parent.spawn((
+                                Text::default(),
+                                LocalizedText::new("menu.back"),
+                                button_text_style(&game_assets),
+                            ));
                         });
                 });
         });
 }
 
-fn sound_settings_menu_setup(mut commands: Commands, volume: Res<Volume>) {
+fn sound_settings_menu_setup(
+    mut commands: Commands,
+    mixer: Res<AudioMixer>,
+    game_assets: Res<GameAssets>,
+) {
     let button_node = Node {
         width: Val::Px(200.0),
         height: Val::Px(65.0),
@@ -438,13 +647,18 @@ fn sound_settings_menu_setup(mut commands: Commands, volume: Res<Volume>) {
     let button_text_style = (
         TextFont {
             font_size: 33.0,
+            font: game_assets.font_ywgh.clone(),
             ..default()
         },
         TextColor(TEXT_COLOR),
     );
+    let small_button_node = Node {
+        width: Val::Px(30.0),
+        height: Val::Px(65.0),
+        margin: UiRect::all(Val::Px(5.0)),
+        ..button_node.clone()
+    };
 
-    let volume = *volume;
-    let button_node_clone = button_node.clone();
     commands
         .spawn((
             Node {
@@ -467,32 +681,66 @@ fn sound_settings_menu_setup(mut commands: Commands, volume: Res<Volume>) {
                     BackgroundColor(CRIMSON.into()),
                 ))
                 .with_children(|parent| {
-                    parent
-                        .spawn((
-                            Node {
-                                align_items: AlignItems::Center,
-                                ..default()
-                            },
-                            BackgroundColor(CRIMSON.into()),
-                        ))
-                        .with_children(|parent| {
-                            parent.spawn((Text::new("音量"), button_text_style.clone()));
-                            for volume_setting in [0, 1, 2, 3, 4, 5, 6, 7, 8, 9] {
+                    // 每条总线一行：名称、音量档位、静音
+                    for (bus, label) in [
+                        (AudioBus::Master, "menu.volume.master"),
+                        (AudioBus::Music, "menu.volume.music"),
+                        (AudioBus::Sfx, "menu.volume.sfx"),
+                        (AudioBus::Ui, "menu.volume.ui"),
+                    ] {
+                        let bus_settings = *mixer.bus(bus);
+                        let current_level =
+                            (bus_settings.volume * VOLUME_LEVELS as f32).round() as u32;
+                        parent
+                            .spawn((
+                                Node {
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                BackgroundColor(CRIMSON.into()),
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Text::default(),
+                                    LocalizedText::new(label),
+                                    button_text_style.clone(),
+                                    Node {
+                                        width: Val::Px(120.0),
+                                        ..default()
+                                    },
+                                ));
+                                for level in 0..=VOLUME_LEVELS {
+                                    let mut entity = parent.spawn((
+                                        Button,
+                                        small_button_node.clone(),
+                                        BackgroundColor(NORMAL_BUTTON),
+                                        VolumeButton { bus, level },
+                                    ));
+                                    if level == current_level {
+                                        entity.insert(SelectedOption);
+                                    }
+                                }
                                 let mut entity = parent.spawn((
                                     Button,
                                     Node {
-                                        width: Val::Px(30.0),
-                                        height: Val::Px(65.0),
-                                        ..button_node_clone.clone()
+                                        width: Val::Px(100.0),
+                                        ..small_button_node.clone()
                                     },
                                     BackgroundColor(NORMAL_BUTTON),
-                                    Volume(volume_setting),
+                                    MuteButton(bus),
                                 ));
-                                if volume == Volume(volume_setting) {
+                                entity.with_children(|parent| {
+                                    parent.spawn((
+                                        Text::default(),
+                                        LocalizedText::new("menu.mute"),
+                                        button_text_style.clone(),
+                                    ));
+                                });
+                                if bus_settings.muted {
                                     entity.insert(SelectedOption);
                                 }
-                            }
-                        });
+                            });
+                    }
                     parent
                         .spawn((
                             Button,
@@ -501,11 +749,117 @@ fn sound_settings_menu_setup(mut commands: Commands, volume: Res<Volume>) {
                             MenuButtonAction::BackToSettings,
                         ))
                         .with_children(|parent| {
-                            parent.spawn((Text::new("返回"), button_text_style));
+                            parent.spawn((
+                                Text::default(),
+                                LocalizedText::new("menu.back"),
+                                button_text_style,
+                            ));
                         });
                 });
         });
 }
+// 所有局的累计统计，内容在进入界面时生成
+fn statistics_menu_setup(
+    mut commands: Commands,
+    lifetime: Res<LifetimeStats>,
+    registry: Res<ItemRegistry>,
+    texts: Res<Texts>,
+    game_assets: Res<GameAssets>,
+) {
+    let text_style = (
+        TextFont {
+            font_size: 28.0,
+            font: texts.font(&game_assets),
+            ..default()
+        },
+        TextColor(TEXT_COLOR),
+        LocalizedFont,
+    );
+
+    let favourite_items = lifetime
+        .favourite_items(3)
+        .into_iter()
+        .map(|(id, pickups)| {
+            let name = registry
+                .find(id)
+                .map_or(id, |item| texts.get(&registry.get(item).name));
+            format!("{name} x{pickups}")
+        })
+        .collect::<Vec<_>>();
+    let favourite_items = if favourite_items.is_empty() {
+        texts.get("lifetime.none").to_string()
+    } else {
+        favourite_items.join(", ")
+    };
+    let most_killed = lifetime
+        .most_killed()
+        .map_or(texts.get("lifetime.none").to_string(), |(enemy, kills)| {
+            format!("{} x{}", texts.get(enemy.key()), kills)
+        });
+    let lines = [
+        texts.format("lifetime.runs_played", &[("count", &lifetime.runs_played)]),
+        texts.format("lifetime.best_depth", &[("depth", &lifetime.best_depth)]),
+        texts.format("lifetime.favourite_items", &[("items", &favourite_items)]),
+        texts.format("lifetime.most_killed", &[("enemy", &most_killed)]),
+    ];
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            OnStatisticsMenuScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::all(Val::Px(20.0)),
+                        row_gap: Val::Px(12.0),
+                        ..default()
+                    },
+                    BackgroundColor(CRIMSON.into()),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::default(),
+                        LocalizedText::new("menu.statistics"),
+                        text_style.clone(),
+                    ));
+                    for line in lines {
+                        parent.spawn((Text::new(line), text_style.clone()));
+                    }
+                    parent
+                        .spawn((
+                            Button,
+                            Node {
+                                width: Val::Px(200.0),
+                                height: Val::Px(65.0),
+                                margin: UiRect::all(Val::Px(20.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor(NORMAL_BUTTON),
+                            MenuButtonAction::BackToMainMenu,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::default(),
+                                LocalizedText::new("menu.back"),
+                                text_style.clone(),
+                            ));
+                        });
+                });
+        });
+}
+
 fn menu_action(
     interaction_query: Query<
         (&Interaction, &MenuButtonAction),
@@ -521,17 +875,23 @@ fn menu_action(
                 MenuButtonAction::Quit => {
                     app_exit_events.send(AppExit::Success);
                 }
+                MenuButtonAction::NewGame => menu_state.set(MenuState::CharacterSelect),
                 MenuButtonAction::Play => {
                     game_state.set(GameState::Gaming);
                     menu_state.set(MenuState::Disabled);
                 }
                 MenuButtonAction::Settings => menu_state.set(MenuState::Settings),
+                MenuButtonAction::Statistics => menu_state.set(MenuState::Statistics),
+                MenuButtonAction::Collection => menu_state.set(MenuState::Collection),
                 MenuButtonAction::SettingsDisplay => {
                     menu_state.set(MenuState::SettingsDisplay);
                 }
                 MenuButtonAction::SettingsSound => {
                     menu_state.set(MenuState::SettingsSound);
                 }
+                MenuButtonAction::SettingsDifficulty => {
+                    menu_state.set(MenuState::SettingsDifficulty);
+                }
                 MenuButtonAction::BackToMainMenu => menu_state.set(MenuState::Main),
                 MenuButtonAction::BackToSettings => {
                     menu_state.set(MenuState::Settings);
@@ -0,0 +1,180 @@
+use bevy::prelude::*;
+
+use crate::{
+    item::ItemRegistry,
+    localization::{LocalizedFont, LocalizedText, Texts},
+    run_stats::RunStats,
+    GameAssets, GameState,
+};
+
+use super::{
+    despawn_screen,
+    init::{MenuButtonAction, NORMAL_BUTTON},
+    TEXT_COLOR,
+};
+
+const ITEM_ICON_SIZE: f32 = 40.0;
+
+pub struct SummaryPlugin;
+
+// 本局结束后显示结算界面，失败和通关共用
+impl Plugin for SummaryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::GameOver), summary_setup)
+            .add_systems(OnEnter(GameState::Win), summary_setup)
+            .add_systems(
+                OnExit(GameState::GameOver),
+                despawn_screen::<OnSummaryScreen>,
+            )
+            .add_systems(OnExit(GameState::Win), despawn_screen::<OnSummaryScreen>);
+    }
+}
+
+#[derive(Component)]
+struct OnSummaryScreen;
+
+// 秒数显示为 分:秒
+fn format_time(seconds: f32) -> String {
+    let seconds = seconds.max(0.0) as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn summary_setup(
+    mut commands: Commands,
+    run_stats: Res<RunStats>,
+    registry: Res<ItemRegistry>,
+    texts: Res<Texts>,
+    game_assets: Res<GameAssets>,
+    game_state: Res<State<GameState>>,
+) {
+    let title = match game_state.get() {
+        GameState::Win => "summary.win",
+        _ => "summary.game_over",
+    };
+    let text_style = (
+        TextFont {
+            font_size: 26.0,
+            font: texts.font(&game_assets),
+            ..default()
+        },
+        TextColor(TEXT_COLOR),
+        LocalizedFont,
+    );
+    let lines = [
+        texts.format("summary.depth", &[("depth", &run_stats.depth)]),
+        texts.format(
+            "summary.rooms_cleared",
+            &[("count", &run_stats.rooms_cleared)],
+        ),
+        texts.format(
+            "summary.time",
+            &[("time", &format_time(run_stats.total_time()))],
+        ),
+        texts.format(
+            "summary.damage",
+            &[
+                ("dealt", &(run_stats.damage_dealt.round() as u32)),
+                ("taken", &(run_stats.damage_taken.round() as u32)),
+            ],
+        ),
+        texts.format("summary.kills", &[("count", &run_stats.total_kills())]),
+        texts.format(
+            "summary.balls",
+            &[
+                ("shot", &run_stats.balls_shot),
+                ("lost", &run_stats.balls_lost),
+            ],
+        ),
+    ];
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            // 盖住游戏画面和 HUD
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            GlobalZIndex(10),
+            OnSummaryScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(10.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::default(),
+                        LocalizedText::new(title),
+                        TextFont {
+                            font_size: 48.0,
+                            font: texts.font(&game_assets),
+                            ..default()
+                        },
+                        TextColor(TEXT_COLOR),
+                    ));
+                    for line in lines {
+                        parent.spawn((Text::new(line), text_style.clone()));
+                    }
+
+                    // 本局获得的道具
+                    parent.spawn((
+                        Text::default(),
+                        LocalizedText::new("summary.items"),
+                        text_style.clone(),
+                    ));
+                    parent
+                        .spawn(Node {
+                            flex_wrap: FlexWrap::Wrap,
+                            justify_content: JustifyContent::Center,
+                            max_width: Val::Px(600.0),
+                            column_gap: Val::Px(6.0),
+                            row_gap: Val::Px(6.0),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            for &item in &run_stats.items {
+                                let def = registry.get(item);
+                                parent.spawn((
+                                    ImageNode::new(registry.icon(item))
+                                        .with_color(def.tint_color()),
+                                    Node {
+                                        width: Val::Px(ITEM_ICON_SIZE),
+                                        height: Val::Px(ITEM_ICON_SIZE),
+                                        ..default()
+                                    },
+                                ));
+                            }
+                        });
+
+                    parent
+                        .spawn((
+                            Button,
+                            Node {
+                                width: Val::Px(200.0),
+                                height: Val::Px(65.0),
+                                margin: UiRect::all(Val::Px(20.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            BackgroundColor(NORMAL_BUTTON),
+                            MenuButtonAction::Quit,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Text::default(),
+                                LocalizedText::new("menu.quit"),
+                                text_style.clone(),
+                            ));
+                        });
+                });
+        });
+}
@@ -0,0 +1,363 @@
+use std::fs::File;
+
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use ron::de::from_reader;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ball::Ball,
+    brick::{
+        self,
+        character::{Character, CharacterRegistry, Passives},
+        inventory::Inventory,
+        item_collection::{AddItemEvent, ItemCollection},
+        Brick,
+    },
+    enemy::Enemy,
+    item::{ItemRegistry, LockedItems},
+    localization::Texts,
+    run_stats::{self, LifetimeStats, RunStats},
+    settings::storage,
+    GameState,
+};
+
+const PROGRESS_FILE: &str = "progress.ron";
+
+pub struct ProgressionPlugin;
+
+impl Plugin for ProgressionPlugin {
+    fn build(&self, app: &mut App) {
+        // 和设置一样在 build 时同步读取，菜单一打开就要用到
+        let progress: Progress = storage::load(PROGRESS_FILE).unwrap_or_default();
+        app.insert_resource(progress)
+            .init_resource::<Progression>()
+            .add_systems(Startup, load_progression)
+            .add_systems(
+                OnEnter(GameState::Gaming),
+                apply_loadout.after(brick::spawn::spawn_brick),
+            )
+            .add_systems(
+                OnExit(GameState::Gaming),
+                award_progress.after(run_stats::finish_run),
+            )
+            .add_systems(Last, save_progress.run_if(progress_changed));
+    }
+}
+
+// 每局结束时获得的碎片
+#[derive(Deserialize, Debug, Clone, Copy, Default)]
+pub struct Rewards {
+    pub per_room_cleared: u32,
+    pub per_kill: u32,
+    pub per_depth: u32,
+}
+
+impl Rewards {
+    pub fn for_run(&self, run: &RunStats) -> u32 {
+        run.rooms_cleared * self.per_room_cleared
+            + run.total_kills() * self.per_kill
+            + run.depth as u32 * self.per_depth
+    }
+}
+
+// 开局时在角色自带的背包之外额外获得的东西
+#[derive(Deserialize, Debug, Clone)]
+pub struct LoadoutDef {
+    pub id: String,
+    // 名称和描述是本地化文本的键
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub extra_capacity: usize,
+    #[serde(default)]
+    pub balls: Vec<Ball>,
+    // 开局自带的道具 id
+    #[serde(default)]
+    pub items: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub enum Unlockable {
+    // 解锁后道具才会出现在道具池里
+    Item(String),
+    // 解锁后开局多一个这种球
+    Ball(Ball),
+    Loadout(String),
+    Character(String),
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum UnlockCondition {
+    // 在收藏界面花费碎片购买
+    Cost(u32),
+    ReachDepth(usize),
+    TotalKills(u32),
+    RunsPlayed(u32),
+    Defeat(Enemy, u32),
+}
+
+impl UnlockCondition {
+    // 成就类的条件在每局结束时检查，花费碎片的条件需要手动购买
+    fn is_met(&self, lifetime: &LifetimeStats) -> bool {
+        match *self {
+            UnlockCondition::Cost(_) => false,
+            UnlockCondition::ReachDepth(depth) => lifetime.best_depth >= depth,
+            UnlockCondition::TotalKills(count) => lifetime.kills.values().sum::<u32>() >= count,
+            UnlockCondition::RunsPlayed(count) => lifetime.runs_played >= count,
+            UnlockCondition::Defeat(enemy, count) => {
+                lifetime.kills.get(&enemy).copied().unwrap_or(0) >= count
+            }
+        }
+    }
+
+    pub fn describe(&self, texts: &Texts) -> String {
+        match *self {
+            UnlockCondition::Cost(cost) => texts.format("unlock.cost", &[("cost", &cost)]),
+            UnlockCondition::ReachDepth(depth) => {
+                texts.format("unlock.reach_depth", &[("depth", &depth)])
+            }
+            UnlockCondition::TotalKills(count) => {
+                texts.format("unlock.total_kills", &[("count", &count)])
+            }
+            UnlockCondition::RunsPlayed(count) => {
+                texts.format("unlock.runs_played", &[("count", &count)])
+            }
+            UnlockCondition::Defeat(enemy, count) => texts.format(
+                "unlock.defeat",
+                &[("enemy", &texts.get(enemy.key())), ("count", &count)],
+            ),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct UnlockDef {
+    pub id: String,
+    pub content: Unlockable,
+    pub condition: UnlockCondition,
+}
+
+// 局外成长的定义，启动时从 progression.ron 读取
+#[derive(Resource, Deserialize, Debug, Default)]
+pub struct Progression {
+    pub rewards: Rewards,
+    pub loadouts: Vec<LoadoutDef>,
+    pub unlocks: Vec<UnlockDef>,
+}
+
+impl Progression {
+    fn unlock_for(&self, content: &Unlockable) -> Option<&UnlockDef> {
+        self.unlocks
+            .iter()
+            .find(|unlock| unlock.content == *content)
+    }
+
+    pub fn loadout(&self, id: &str) -> Option<&LoadoutDef> {
+        self.loadouts.iter().find(|loadout| loadout.id == id)
+    }
+}
+
+// 保存在本地的进度，缺失的字段使用默认值
+#[derive(Resource, Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct Progress {
+    pub currency: u32,
+    // 已解锁的 UnlockDef id
+    pub unlocked: HashSet<String>,
+    // 选中的初始配置，空的时候用第一个
+    pub loadout: String,
+    // 选中的角色，空的时候用第一个
+    pub character: String,
+}
+
+impl Progress {
+    pub fn is_unlocked(&self, unlock: &UnlockDef) -> bool {
+        self.unlocked.contains(&unlock.id)
+    }
+
+    // 没有对应解锁项的内容默认可用
+    pub fn has(&self, progression: &Progression, content: &Unlockable) -> bool {
+        progression
+            .unlock_for(content)
+            .is_none_or(|unlock| self.is_unlocked(unlock))
+    }
+
+    // 花费碎片解锁，碎片不够时返回 false
+    pub fn buy(&mut self, unlock: &UnlockDef) -> bool {
+        let UnlockCondition::Cost(cost) = unlock.condition else {
+            return false;
+        };
+        if self.is_unlocked(unlock) || self.currency < cost {
+            return false;
+        }
+        self.currency -= cost;
+        self.unlocked.insert(unlock.id.clone());
+        true
+    }
+
+    // 选中的配置被删掉或者还没解锁时退回第一个
+    pub fn current_loadout<'a>(&self, progression: &'a Progression) -> Option<&'a LoadoutDef> {
+        progression
+            .loadout(&self.loadout)
+            .filter(|loadout| self.has(progression, &Unlockable::Loadout(loadout.id.clone())))
+            .or_else(|| progression.loadouts.first())
+    }
+
+    // 和初始配置一样，选中的角色不可用时退回第一个
+    pub fn current_character(
+        &self,
+        progression: &Progression,
+        characters: &CharacterRegistry,
+    ) -> Character {
+        characters
+            .find(&self.character)
+            .filter(|&character| {
+                let id = characters.get(character).id.clone();
+                self.has(progression, &Unlockable::Character(id))
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn load_progression(mut commands: Commands) {
+    let file =
+        File::open("assets/progression/progression.ron").expect("Failed to open progression.ron");
+    let progression: Progression = from_reader(file).expect("Unable to load progression.ron");
+    if progression.loadouts.is_empty() {
+        panic!("progression.ron needs at least one loadout");
+    }
+    commands.insert_resource(progression);
+}
+
+// 开局的球按角色、配置、解锁的顺序放进背包，装不下的不要
+fn starting_inventory(capacity: usize, mut balls: Vec<Ball>) -> Inventory {
+    balls.truncate(capacity);
+    Inventory::new(capacity, &balls)
+}
+
+// 按每个砖块的角色和配置准备背包和被动，配置里的道具是共享的，同时更新道具池里还没解锁的道具
+fn apply_loadout(
+    progress: Res<Progress>,
+    progression: Res<Progression>,
+    registry: Res<ItemRegistry>,
+    characters: Res<CharacterRegistry>,
+    mut q_brick: Query<(&Character, &mut Inventory, &mut Passives), With<Brick>>,
+    mut collection: ResMut<ItemCollection>,
+    mut locked_items: ResMut<LockedItems>,
+    mut add_item_events: EventWriter<AddItemEvent>,
+) {
+    locked_items.0 = progression
+        .unlocks
+        .iter()
+        .filter(|unlock| !progress.is_unlocked(unlock))
+        .filter_map(|unlock| match &unlock.content {
+            Unlockable::Item(id) => registry.find(id),
+            _ => None,
+        })
+        .collect();
+
+    let Some(loadout) = progress.current_loadout(&progression) else {
+        return;
+    };
+    let unlocked_balls: Vec<Ball> = progression
+        .unlocks
+        .iter()
+        .filter(|unlock| progress.is_unlocked(unlock))
+        .filter_map(|unlock| match unlock.content {
+            Unlockable::Ball(ball) => Some(ball),
+            _ => None,
+        })
+        .collect();
+
+    for (character, mut inventory, mut passives) in q_brick.iter_mut() {
+        let character = characters.get(*character);
+        let mut balls = character.inventory.balls.clone();
+        balls.extend(loadout.balls.iter().copied());
+        balls.extend(unlocked_balls.iter().copied());
+        let capacity = character.inventory.capacity + loadout.extra_capacity;
+        if balls.len() > capacity {
+            warn!(
+                "character {} starts with {} balls but only has room for {}",
+                character.id,
+                balls.len(),
+                capacity
+            );
+        }
+        *inventory = starting_inventory(capacity, balls);
+        passives.0 = character
+            .passive
+            .iter()
+            .filter_map(|id| {
+                let item = registry.find(id);
+                if item.is_none() {
+                    warn!(
+                        "unknown passive item id in character {}: {}",
+                        character.id, id
+                    );
+                }
+                item
+            })
+            .collect();
+    }
+
+    *collection = ItemCollection::default();
+    for id in loadout.items.iter() {
+        match registry.find(id) {
+            Some(item) => {
+                collection.add(item);
+                add_item_events.send(AddItemEvent(item));
+            }
+            None => warn!("unknown item id in loadout {}: {}", loadout.id, id),
+        }
+    }
+}
+
+// 结算碎片并检查成就类的解锁条件
+fn award_progress(
+    mut progress: ResMut<Progress>,
+    progression: Res<Progression>,
+    run_stats: Res<RunStats>,
+    lifetime: Res<LifetimeStats>,
+) {
+    progress.currency += progression.rewards.for_run(&run_stats);
+    for unlock in progression.unlocks.iter() {
+        if unlock.condition.is_met(&lifetime) {
+            progress.unlocked.insert(unlock.id.clone());
+        }
+    }
+}
+
+// 资源在插入时也算作changed，这里排除掉启动时的第一次
+fn progress_changed(progress: Res<Progress>) -> bool {
+    progress.is_changed() && !progress.is_added()
+}
+
+fn save_progress(progress: Res<Progress>) {
+    storage::save(PROGRESS_FILE, &*progress);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starting_inventory_drops_balls_beyond_capacity() {
+        let mut inventory = starting_inventory(2, vec![Ball::Tennis; 5]);
+        assert_eq!(inventory.capacity(), 2);
+        assert_eq!(inventory.index(), 2);
+        assert_eq!(inventory.peek(), Some(Ball::Tennis));
+        assert_eq!(inventory.pop(), Some(Ball::Tennis));
+        assert_eq!(inventory.pop(), Some(Ball::Tennis));
+        assert_eq!(inventory.pop(), None);
+    }
+
+    #[test]
+    fn starting_inventory_keeps_room_for_more_balls() {
+        let mut inventory = starting_inventory(3, vec![Ball::Tennis]);
+        assert_eq!(inventory.index(), 1);
+        assert!(inventory.push(Ball::Tennis).is_ok());
+        assert!(inventory.push(Ball::Tennis).is_ok());
+        assert!(inventory.push(Ball::Tennis).is_err());
+    }
+}
@@ -0,0 +1,197 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ball::Ball,
+    brick::{item_collection::AddItemEvent, stats::BrickStats, Brick},
+    enemy::Enemy,
+    events::{BallLostEvent, BallShotEvent, DamageEvent, EnemyDeathEvent},
+    item::{Item, ItemRegistry},
+    settings::storage,
+    world::map::room::{
+        control::ChooseState,
+        loading::{CurrentRoom, LoadingState},
+    },
+    GameState,
+};
+
+const LIFETIME_STATS_FILE: &str = "stats.ron";
+
+pub struct RunStatsPlugin;
+
+impl Plugin for RunStatsPlugin {
+    fn build(&self, app: &mut App) {
+        let lifetime: LifetimeStats = storage::load(LIFETIME_STATS_FILE).unwrap_or_default();
+        app.insert_resource(lifetime)
+            .init_resource::<RunStats>()
+            .add_systems(OnEnter(GameState::Gaming), reset_run_stats)
+            .add_systems(OnEnter(LoadingState::FadeIn), start_room_timer)
+            .add_systems(OnEnter(ChooseState::Choosing), record_room_cleared)
+            .add_systems(
+                Update,
+                (
+                    record_damage,
+                    record_kills,
+                    record_shots,
+                    record_lost_balls,
+                    record_items,
+                )
+                    .run_if(in_state(GameState::Gaming)),
+            )
+            .add_systems(OnExit(GameState::Gaming), finish_run);
+    }
+}
+
+// 本局的统计，结算界面显示
+#[derive(Resource, Default, Debug)]
+pub struct RunStats {
+    // 球对敌人造成的伤害
+    pub damage_dealt: f32,
+    // 砖块受到的压力
+    pub damage_taken: f32,
+    pub kills: HashMap<Enemy, u32>,
+    pub balls_shot: u32,
+    pub balls_lost: u32,
+    pub items: Vec<Item>,
+    pub rooms_cleared: u32,
+    // 每个房间停留的秒数
+    pub room_times: Vec<f32>,
+    pub depth: usize,
+    room_started: Option<f32>,
+}
+
+impl RunStats {
+    pub fn total_kills(&self) -> u32 {
+        self.kills.values().sum()
+    }
+
+    pub fn total_time(&self) -> f32 {
+        self.room_times.iter().sum()
+    }
+
+    // 离开当前房间时记下停留的时间
+    fn end_room(&mut self, now: f32) {
+        if let Some(started) = self.room_started.take() {
+            self.room_times.push(now - started);
+        }
+    }
+}
+
+// 所有局的累计统计，保存在本地
+#[derive(Resource, Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct LifetimeStats {
+    pub runs_played: u32,
+    pub best_depth: usize,
+    // 按道具 id 记录拾取次数，道具的序号会随 items.ron 变化
+    pub item_pickups: HashMap<String, u32>,
+    pub kills: HashMap<Enemy, u32>,
+}
+
+impl LifetimeStats {
+    // 拾取次数最多的几个道具，次数相同时按 id 排序保证顺序稳定
+    pub fn favourite_items(&self, count: usize) -> Vec<(&str, u32)> {
+        let mut items = self
+            .item_pickups
+            .iter()
+            .map(|(id, &pickups)| (id.as_str(), pickups))
+            .collect::<Vec<_>>();
+        items.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        items.truncate(count);
+        items
+    }
+
+    pub fn most_killed(&self) -> Option<(Enemy, u32)> {
+        self.kills
+            .iter()
+            .max_by_key(|(_, &kills)| kills)
+            .map(|(&enemy, &kills)| (enemy, kills))
+    }
+
+    fn add_run(&mut self, run: &RunStats, registry: &ItemRegistry) {
+        self.runs_played += 1;
+        self.best_depth = self.best_depth.max(run.depth);
+        for &item in &run.items {
+            *self
+                .item_pickups
+                .entry(registry.get(item).id.clone())
+                .or_default() += 1;
+        }
+        for (&enemy, &kills) in &run.kills {
+            *self.kills.entry(enemy).or_default() += kills;
+        }
+    }
+}
+
+fn reset_run_stats(mut run_stats: ResMut<RunStats>, time: Res<Time>) {
+    *run_stats = RunStats {
+        room_started: Some(time.elapsed_secs()),
+        ..default()
+    };
+}
+
+fn start_room_timer(mut run_stats: ResMut<RunStats>, time: Res<Time>) {
+    let now = time.elapsed_secs();
+    run_stats.end_room(now);
+    run_stats.room_started = Some(now);
+}
+
+fn record_room_cleared(mut run_stats: ResMut<RunStats>, current_room: Res<CurrentRoom>) {
+    if current_room.has_encounter {
+        run_stats.rooms_cleared += 1;
+    }
+}
+
+fn record_damage(
+    mut damage_events: EventReader<DamageEvent>,
+    mut run_stats: ResMut<RunStats>,
+    q_ball: Query<(), With<Ball>>,
+    q_enemy: Query<(), With<Enemy>>,
+    q_brick: Query<(), With<Brick>>,
+) {
+    for event in damage_events.read() {
+        if q_ball.contains(event.offender) && q_enemy.contains(event.victim) {
+            run_stats.damage_dealt += event.damage.value;
+        } else if q_brick.contains(event.victim) {
+            run_stats.damage_taken += event.damage.value;
+        }
+    }
+}
+
+fn record_kills(mut death_events: EventReader<EnemyDeathEvent>, mut run_stats: ResMut<RunStats>) {
+    for event in death_events.read() {
+        *run_stats.kills.entry(event.enemy).or_default() += 1;
+    }
+}
+
+fn record_shots(mut shot_events: EventReader<BallShotEvent>, mut run_stats: ResMut<RunStats>) {
+    run_stats.balls_shot += shot_events.read().count() as u32;
+}
+
+fn record_lost_balls(
+    mut ball_lost_events: EventReader<BallLostEvent>,
+    mut run_stats: ResMut<RunStats>,
+) {
+    run_stats.balls_lost += ball_lost_events.read().count() as u32;
+}
+
+fn record_items(mut add_item_events: EventReader<AddItemEvent>, mut run_stats: ResMut<RunStats>) {
+    for &AddItemEvent(item) in add_item_events.read() {
+        run_stats.items.push(item);
+    }
+}
+
+// 离开游戏状态即本局结束，累计到总统计里并保存
+pub fn finish_run(
+    mut run_stats: ResMut<RunStats>,
+    mut lifetime: ResMut<LifetimeStats>,
+    brick_stats: Res<BrickStats>,
+    registry: Res<ItemRegistry>,
+    time: Res<Time>,
+) {
+    run_stats.end_room(time.elapsed_secs());
+    run_stats.depth = brick_stats.current_room;
+    lifetime.add_run(&run_stats, &registry);
+    storage::save(LIFETIME_STATS_FILE, &*lifetime);
+}
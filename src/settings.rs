@@ -0,0 +1,108 @@
+pub mod storage;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    audio::AudioMixer,
+    brick::input::{KeyBindings, PlayerTwoBindings},
+    difficulty::{CustomDifficulty, Difficulty},
+    menu::DisplayQuality,
+};
+
+const SETTINGS_FILE: &str = "settings.ron";
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        // 设置需要在其他插件使用之前就存在，所以在build时同步读取
+        let settings: Settings = storage::load(SETTINGS_FILE).unwrap_or_default();
+        app.insert_resource(settings.audio)
+            .insert_resource(settings.display)
+            .insert_resource(settings.key_bindings)
+            .insert_resource(settings.player_two_bindings)
+            .insert_resource(settings.language)
+            .insert_resource(settings.difficulty)
+            .insert_resource(settings.custom_difficulty)
+            .add_systems(Last, save_settings.run_if(settings_changed));
+    }
+}
+
+#[derive(
+    Resource, Component, Serialize, Deserialize, Debug, Default, PartialEq, Eq, Hash, Clone, Copy,
+)]
+pub enum Language {
+    #[default]
+    ZhCn,
+    En,
+}
+
+// 存档文件的内容，缺失的字段使用默认值，这样新增设置项时旧的文件依然可以读取
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Settings {
+    pub audio: AudioMixer,
+    pub display: DisplayQuality,
+    pub key_bindings: KeyBindings,
+    // 双人模式下第二个玩家的键盘按键
+    pub player_two_bindings: PlayerTwoBindings,
+    pub language: Language,
+    pub difficulty: Difficulty,
+    // 选择自定义难度时使用的倍率
+    pub custom_difficulty: CustomDifficulty,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            audio: AudioMixer::default(),
+            display: DisplayQuality::Medium,
+            key_bindings: KeyBindings::default(),
+            player_two_bindings: PlayerTwoBindings::default(),
+            language: Language::default(),
+            difficulty: Difficulty::default(),
+            custom_difficulty: CustomDifficulty::default(),
+        }
+    }
+}
+
+// 资源在插入时也算作changed，这里排除掉启动时的第一次
+fn settings_changed(
+    audio: Res<AudioMixer>,
+    display: Res<DisplayQuality>,
+    key_bindings: Res<KeyBindings>,
+    player_two_bindings: Res<PlayerTwoBindings>,
+    language: Res<Language>,
+    difficulty: Res<Difficulty>,
+    custom_difficulty: Res<CustomDifficulty>,
+) -> bool {
+    (audio.is_changed() && !audio.is_added())
+        || (display.is_changed() && !display.is_added())
+        || (key_bindings.is_changed() && !key_bindings.is_added())
+        || (player_two_bindings.is_changed() && !player_two_bindings.is_added())
+        || (language.is_changed() && !language.is_added())
+        || (difficulty.is_changed() && !difficulty.is_added())
+        || (custom_difficulty.is_changed() && !custom_difficulty.is_added())
+}
+
+fn save_settings(
+    audio: Res<AudioMixer>,
+    display: Res<DisplayQuality>,
+    key_bindings: Res<KeyBindings>,
+    player_two_bindings: Res<PlayerTwoBindings>,
+    language: Res<Language>,
+    difficulty: Res<Difficulty>,
+    custom_difficulty: Res<CustomDifficulty>,
+) {
+    let settings = Settings {
+        audio: audio.clone(),
+        display: *display,
+        key_bindings: key_bindings.clone(),
+        player_two_bindings: player_two_bindings.clone(),
+        language: *language,
+        difficulty: *difficulty,
+        custom_difficulty: *custom_difficulty,
+    };
+    storage::save(SETTINGS_FILE, &settings);
+}
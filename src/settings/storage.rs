@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+use ron::ser::PrettyConfig;
+use serde::{de::DeserializeOwned, Serialize};
+
+const APP_NAME: &str = "another_brick_in_the_wall";
+
+// 读取存档文件，文件不存在或者损坏时返回 None，由调用者回退到默认值
+pub fn load<T: DeserializeOwned>(file: &str) -> Option<T> {
+    let content = read(file)?;
+    match ron::from_str::<T>(&content) {
+        Ok(value) => Some(value),
+        Err(e) => {
+            warn!("{file} is corrupt, falling back to defaults: {e}");
+            None
+        }
+    }
+}
+
+pub fn save<T: Serialize>(file: &str, value: &T) {
+    match ron::ser::to_string_pretty(value, PrettyConfig::default()) {
+        Ok(content) => write(file, &content),
+        Err(e) => warn!("failed to serialize {file}: {e}"),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn file_path(file: &str) -> Option<std::path::PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_NAME).join(file))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read(file: &str) -> Option<String> {
+    std::fs::read_to_string(file_path(file)?).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write(file: &str, content: &str) {
+    let Some(path) = file_path(file) else {
+        warn!("no config directory on this platform, {file} is not saved");
+        return;
+    };
+    if let Some(dir) = path.parent() {
+        if let Err(e) = std::fs::create_dir_all(dir) {
+            warn!("failed to create config directory: {e}");
+            return;
+        }
+    }
+    if let Err(e) = std::fs::write(&path, content) {
+        warn!("failed to write {}: {e}", path.display());
+    }
+}
+
+// 网页端存到 localStorage
+#[cfg(target_arch = "wasm32")]
+fn storage_key(file: &str) -> String {
+    format!("{APP_NAME}/{file}")
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read(file: &str) -> Option<String> {
+    local_storage()?.get_item(&storage_key(file)).ok().flatten()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write(file: &str, content: &str) {
+    let Some(storage) = local_storage() else {
+        warn!("localStorage is not available, {file} is not saved");
+        return;
+    };
+    if storage.set_item(&storage_key(file), content).is_err() {
+        warn!("failed to write {file} to localStorage");
+    }
+}
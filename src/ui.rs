@@ -1,8 +1,12 @@
+mod ability_cooldown;
+mod active_item;
 mod cursor;
+mod enemy_bar;
+mod hud;
 mod inventory;
-mod pressure_bar;
+mod stats_panel;
 
-pub mod item_pickup_hint;
+pub mod notification;
 
 use bevy::prelude::*;
 
@@ -13,10 +17,14 @@ pub struct UiPlugin;
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
-            pressure_bar::PressureBarPlugin,
+            hud::HudPlugin,
+            enemy_bar::EnemyBarPlugin,
             cursor::CursorPlugin,
             inventory::InventoryPlugin,
-            item_pickup_hint::ItemPickUpHintPlugin,
+            notification::NotificationPlugin,
+            ability_cooldown::AbilityCooldownPlugin,
+            stats_panel::StatsPanelPlugin,
+            active_item::ActiveItemPlugin,
         ));
     }
 }
@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+
+use crate::{
+    brick::abilities::{ChargeShot, Dash},
+    brick::{player::Player, Brick},
+    localization::LocalizedText,
+    GameAssets, GameState, WINDOW_HEIGHT, WINDOW_WIDTH,
+};
+
+const BAR_WIDTH: f32 = 60.0;
+const BAR_HEIGHT: f32 = 8.0;
+const READY_COLOR: Color = Color::srgb(0.3, 0.8, 0.4);
+const COOLDOWN_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
+const CHARGING_COLOR: Color = Color::srgb(1.0, 0.75, 0.2);
+
+#[derive(Component, Clone, Copy)]
+enum Ability {
+    Dash,
+    Charge,
+}
+
+// 冷却条前景，记录属于哪个砖块
+#[derive(Component)]
+struct CooldownBarForeground(Ability, Entity);
+
+pub struct AbilityCooldownPlugin;
+
+impl Plugin for AbilityCooldownPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (spawn_cooldown_bars, update_cooldown_bars)
+                .chain()
+                .run_if(in_state(GameState::Gaming)),
+        );
+    }
+}
+
+// 每个砖块一组冷却条，2P 的在 1P 的上面一行
+fn spawn_cooldown_bars(
+    mut commands: Commands,
+    q_brick: Query<(Entity, &Player), Added<Brick>>,
+    assets: Res<GameAssets>,
+) {
+    for (brick, player) in q_brick.iter() {
+        // 放在压力条右边
+        for (index, (ability, label)) in
+            [(Ability::Dash, "hud.dash"), (Ability::Charge, "hud.charge")]
+                .into_iter()
+                .enumerate()
+        {
+            commands
+                .spawn((
+                    Sprite {
+                        color: Color::srgb(0.25, 0.25, 0.25),
+                        custom_size: Some(Vec2::new(BAR_WIDTH + 4.0, BAR_HEIGHT + 4.0)),
+                        ..default()
+                    },
+                    Transform::from_xyz(
+                        (-WINDOW_WIDTH / 2.0) + 190.0 + index as f32 * 80.0,
+                        (-WINDOW_HEIGHT / 2.0) + 20.0 + player.index() as f32 * 40.0,
+                        2.0,
+                    ),
+                    ability,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Sprite {
+                            color: READY_COLOR,
+                            custom_size: Some(Vec2::new(BAR_WIDTH, BAR_HEIGHT)),
+                            ..default()
+                        },
+                        Transform::from_xyz(0.0, 0.0, 2.0),
+                        CooldownBarForeground(ability, brick),
+                    ));
+
+                    parent.spawn((
+                        Text2d::default(),
+                        LocalizedText::new(label),
+                        TextFont {
+                            font: assets.font.clone(),
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextLayout::new_with_justify(JustifyText::Center),
+                        TextColor(Color::WHITE),
+                        Transform::from_xyz(0.0, 16.0, 2.0),
+                    ));
+                });
+        }
+    }
+}
+
+fn update_cooldown_bars(
+    q_brick: Query<(&Dash, &ChargeShot), With<Brick>>,
+    mut q_foreground: Query<(&CooldownBarForeground, &mut Transform, &mut Sprite)>,
+) {
+    for (foreground, mut transform, mut sprite) in q_foreground.iter_mut() {
+        let Ok((dash, charge)) = q_brick.get(foreground.1) else {
+            continue;
+        };
+        // 蓄力时显示蓄力进度，否则显示冷却进度
+        let (ratio, color) = match foreground.0 {
+            Ability::Dash => cooldown(dash.cooldown_fraction()),
+            Ability::Charge if charge.ratio > 0.0 => (charge.ratio, CHARGING_COLOR),
+            Ability::Charge => cooldown(charge.cooldown_fraction()),
+        };
+        // 保持左对齐
+        transform.scale.x = ratio;
+        transform.translation.x = -(1.0 - ratio) * BAR_WIDTH / 2.0;
+        sprite.color = color;
+    }
+}
+
+fn cooldown(fraction: f32) -> (f32, Color) {
+    let color = if fraction >= 1.0 {
+        READY_COLOR
+    } else {
+        COOLDOWN_COLOR
+    };
+    (fraction, color)
+}
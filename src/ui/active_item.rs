@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+
+use crate::{
+    item::{active::ActiveItemSlot, ItemRegistry},
+    GameState,
+};
+
+const ICON_SIZE: f32 = 40.0;
+
+#[derive(Component)]
+struct ActiveItemIcon;
+
+#[derive(Component)]
+struct ActiveItemChargeBar;
+
+pub struct ActiveItemPlugin;
+
+impl Plugin for ActiveItemPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Gaming), spawn_active_item_ui)
+            .add_systems(
+                Update,
+                update_active_item_ui.run_if(in_state(GameState::Gaming)),
+            );
+    }
+}
+
+// 放在背包上方：道具图标和竖直的充能条
+fn spawn_active_item_ui(mut commands: Commands) {
+    commands
+        .spawn(Node {
+            bottom: Val::Px(40.0),
+            right: Val::Px(33.0),
+            position_type: PositionType::Absolute,
+            column_gap: Val::Px(4.0),
+            align_items: AlignItems::End,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    width: Val::Px(ICON_SIZE),
+                    height: Val::Px(ICON_SIZE),
+                    ..default()
+                },
+                ImageNode::default(),
+                Visibility::Hidden,
+                ActiveItemIcon,
+            ));
+            // 充能条背景
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(8.0),
+                        height: Val::Px(ICON_SIZE),
+                        flex_direction: FlexDirection::ColumnReverse,
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(0.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.3, 0.8, 0.4)),
+                        ActiveItemChargeBar,
+                    ));
+                });
+        });
+}
+
+fn update_active_item_ui(
+    slot: Res<ActiveItemSlot>,
+    registry: Res<ItemRegistry>,
+    icon: Single<(&mut ImageNode, &mut Visibility), With<ActiveItemIcon>>,
+    charge_bar: Single<(&mut Node, &mut BackgroundColor), With<ActiveItemChargeBar>>,
+) {
+    if !slot.is_changed() {
+        return;
+    }
+    let (mut image, mut visibility) = icon.into_inner();
+    match slot.item {
+        Some(item) => {
+            image.image = registry.icon(item);
+            image.color = registry.get(item).tint_color();
+            *visibility = Visibility::Inherited;
+        }
+        None => *visibility = Visibility::Hidden,
+    }
+
+    // 充满时变成金色
+    let fraction = slot.fraction(&registry);
+    let (mut node, mut color) = charge_bar.into_inner();
+    node.height = Val::Percent(fraction * 100.0);
+    color.0 = if fraction >= 1.0 {
+        Color::srgb(1.0, 0.8, 0.2)
+    } else {
+        Color::srgb(0.3, 0.8, 0.4)
+    };
+}
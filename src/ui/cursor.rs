@@ -3,14 +3,19 @@ use std::f32::consts::FRAC_PI_2;
 use bevy::prelude::*;
 
 use crate::{
-    brick::{input::PlayerInput, Brick},
+    brick::{
+        input::PlayerInput,
+        player::{PlayMode, Player},
+        Brick,
+    },
     GameAssets, GameState,
 };
 
 pub const RADIUS: f32 = 150.0;
 pub const ANGLE_VELOCITY: f32 = 2.0;
 
-#[derive(Resource)]
+// 每个砖块自己的瞄准角度
+#[derive(Component)]
 pub struct AimAngle(pub f32);
 
 impl Default for AimAngle {
@@ -19,60 +24,82 @@ impl Default for AimAngle {
     }
 }
 
+// 准星跟随的砖块
 #[derive(Component)]
-pub struct Cursor;
+pub struct Cursor(pub Entity);
 
 pub struct CursorPlugin;
 
 impl Plugin for CursorPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Gaming), spawn_cursor)
-            .add_systems(Update, (update_cursor,).run_if(in_state(GameState::Gaming)))
-            .init_resource::<AimAngle>();
+        app.add_systems(
+            Update,
+            (spawn_cursor, update_cursor)
+                .chain()
+                .run_if(in_state(GameState::Gaming)),
+        );
     }
 }
 
-fn spawn_cursor(mut commands: Commands, assets: Res<GameAssets>) {
-    commands.spawn((
-        Sprite::from_image(assets.cursor_texture.clone()),
-        Transform::from_xyz(100.0, 0.0, 2.0),
-        Visibility::Hidden,
-        Cursor,
-    ));
+// 每个新生成的砖块一个准星，双人时染上玩家的颜色
+fn spawn_cursor(
+    mut commands: Commands,
+    q_brick: Query<(Entity, &Player), Added<Brick>>,
+    play_mode: Res<PlayMode>,
+    assets: Res<GameAssets>,
+) {
+    for (brick, player) in q_brick.iter() {
+        let mut sprite = Sprite::from_image(assets.cursor_texture.clone());
+        if *play_mode == PlayMode::Coop {
+            sprite.color = player.color();
+        }
+        commands.spawn((
+            sprite,
+            Transform::from_xyz(100.0, 0.0, 2.0),
+            Visibility::Hidden,
+            Cursor(brick),
+        ));
+    }
 }
 
 fn update_cursor(
-    mut cursor: Single<(&mut Transform, &mut Visibility, &mut Sprite), With<Cursor>>,
-    brick_transform: Single<&Transform, (With<Brick>, Without<Cursor>)>,
-    mut player_input: ResMut<PlayerInput>,
+    mut q_cursor: Query<(&Cursor, &mut Transform, &mut Visibility, &mut Sprite)>,
+    mut q_brick: Query<
+        (&Transform, &mut PlayerInput, &mut AimAngle),
+        (With<Brick>, Without<Cursor>),
+    >,
     time: Res<Time>,
     assets: Res<GameAssets>,
-    mut angle: ResMut<AimAngle>,
 ) {
-    if !player_input.toggle_aim {
-        *cursor.1 = Visibility::Hidden;
-        *angle = AimAngle::default();
-        return;
-    }
-    *cursor.1 = Visibility::Visible;
+    for (cursor, mut cursor_transform, mut visibility, mut sprite) in q_cursor.iter_mut() {
+        let Ok((brick_transform, mut player_input, mut angle)) = q_brick.get_mut(cursor.0) else {
+            continue;
+        };
+        if !player_input.toggle_aim {
+            *visibility = Visibility::Hidden;
+            *angle = AimAngle::default();
+            continue;
+        }
+        *visibility = Visibility::Visible;
 
-    angle.0 += player_input.aim_arrow_rotation * ANGLE_VELOCITY * time.delta_secs();
-    angle.0 = angle.0.clamp(15.0_f32.to_radians(), 165.0_f32.to_radians());
-    // 圆参数方程求箭头位置
-    let x = brick_transform.translation.x + angle.0.cos() * RADIUS;
-    let y = brick_transform.translation.y + angle.0.sin() * RADIUS;
-    let target = Vec2::new(x, y);
+        angle.0 += player_input.aim_arrow_rotation * ANGLE_VELOCITY * time.delta_secs();
+        angle.0 = angle.0.clamp(15.0_f32.to_radians(), 165.0_f32.to_radians());
+        // 圆参数方程求箭头位置
+        let x = brick_transform.translation.x + angle.0.cos() * RADIUS;
+        let y = brick_transform.translation.y + angle.0.sin() * RADIUS;
+        let target = Vec2::new(x, y);
 
-    // 调整cursor
-    cursor.0.rotation = Quat::from_rotation_z(angle.0 - FRAC_PI_2);
-    cursor.0.translation = target.extend(2.0);
+        // 调整cursor
+        cursor_transform.rotation = Quat::from_rotation_z(angle.0 - FRAC_PI_2);
+        cursor_transform.translation = target.extend(2.0);
 
-    // 如果cursor 在brick的下边，则改变image且不可发射
-    // 使用angle.0.clamp 之后这段其实可以删掉
-    if !(angle.0 > 15.0_f32.to_radians() && angle.0 < 165.0_f32.to_radians()) {
-        player_input.shoot = false;
-        cursor.2.image = assets.cursor_invalid_texture.clone();
-    } else {
-        cursor.2.image = assets.cursor_texture.clone();
+        // 如果cursor 在brick的下边，则改变image且不可发射
+        // 使用angle.0.clamp 之后这段其实可以删掉
+        if !(angle.0 > 15.0_f32.to_radians() && angle.0 < 165.0_f32.to_radians()) {
+            player_input.shoot = false;
+            sprite.image = assets.cursor_invalid_texture.clone();
+        } else {
+            sprite.image = assets.cursor_texture.clone();
+        }
     }
 }
@@ -0,0 +1,112 @@
+use bevy::{prelude::*, sprite::Anchor};
+
+use crate::{brick::stats::Pressure, enemy::Enemy, GameState};
+
+const BAR_WIDTH: f32 = 40.0;
+const BAR_HEIGHT: f32 = 5.0;
+// 取不到贴图大小时使用的敌人高度
+const DEFAULT_ENEMY_HEIGHT: f32 = 64.0;
+
+pub struct EnemyBarPlugin;
+
+impl Plugin for EnemyBarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (spawn_enemy_bars, update_enemy_bars).run_if(in_state(GameState::Gaming)),
+        );
+    }
+}
+
+// 敌人身上记录自己的压力条前景
+#[derive(Component)]
+struct EnemyBar {
+    background: Entity,
+    foreground: Entity,
+}
+
+fn sprite_height(
+    sprite: &Sprite,
+    images: &Assets<Image>,
+    layouts: &Assets<TextureAtlasLayout>,
+) -> f32 {
+    if let Some(size) = sprite.custom_size {
+        return size.y;
+    }
+    // 动画贴图取单帧的大小
+    if let Some(atlas) = &sprite.texture_atlas {
+        if let Some(rect) = layouts
+            .get(&atlas.layout)
+            .and_then(|layout| layout.textures.get(atlas.index))
+        {
+            return rect.height() as f32;
+        }
+    }
+    images
+        .get(&sprite.image)
+        .map_or(DEFAULT_ENEMY_HEIGHT, |image| image.height() as f32)
+}
+
+// 在每个敌人头顶放一个小压力条，受到伤害之前不显示
+fn spawn_enemy_bars(
+    mut commands: Commands,
+    q_enemy: Query<(Entity, Option<&Sprite>), (Added<Enemy>, With<Pressure>)>,
+    images: Res<Assets<Image>>,
+    layouts: Res<Assets<TextureAtlasLayout>>,
+) {
+    for (entity, sprite) in q_enemy.iter() {
+        let height = sprite.map_or(DEFAULT_ENEMY_HEIGHT, |sprite| {
+            sprite_height(sprite, &images, &layouts)
+        });
+        let foreground = commands
+            .spawn((
+                Sprite {
+                    color: Color::srgb(0.9, 0.2, 0.2),
+                    custom_size: Some(Vec2::new(BAR_WIDTH, BAR_HEIGHT)),
+                    anchor: Anchor::CenterLeft,
+                    ..default()
+                },
+                // 左对齐，只需要缩放宽度
+                Transform::from_xyz(-BAR_WIDTH / 2.0, 0.0, 0.1)
+                    .with_scale(Vec3::new(0.0, 1.0, 1.0)),
+            ))
+            .id();
+        let background = commands
+            .spawn((
+                Sprite::from_color(
+                    Color::srgba(0.1, 0.1, 0.1, 0.8),
+                    Vec2::new(BAR_WIDTH + 2.0, BAR_HEIGHT + 2.0),
+                ),
+                Transform::from_xyz(0.0, height / 2.0 + 8.0, 5.0),
+                Visibility::Hidden,
+            ))
+            .add_child(foreground)
+            .id();
+        commands
+            .entity(entity)
+            .add_child(background)
+            .insert(EnemyBar {
+                background,
+                foreground,
+            });
+    }
+}
+
+fn update_enemy_bars(
+    q_enemy: Query<(&Pressure, &EnemyBar), Changed<Pressure>>,
+    mut q_bar: Query<(&mut Transform, &mut Visibility)>,
+) {
+    for (pressure, bar) in q_enemy.iter() {
+        let ratio = (pressure.current / pressure.max).clamp(0.0, 1.0);
+        if let Ok((mut transform, _)) = q_bar.get_mut(bar.foreground) {
+            transform.scale.x = ratio;
+        }
+        if let Ok((_, mut visibility)) = q_bar.get_mut(bar.background) {
+            *visibility = if ratio > 0.0 {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use bevy::prelude::*;
+
+use crate::{
+    brick::{
+        item_collection::ItemCollection,
+        player::{PlayMode, Player},
+        stats::{BrickStats, Pressure},
+        Brick,
+    },
+    enemy::Enemy,
+    item::{Item, ItemRegistry},
+    localization::{LocalizedFont, Texts},
+    world::map::room::{control::ChooseState, loading::CurrentRoom, RoomType},
+    GameAssets, GameState,
+};
+
+const ITEM_ICON_SIZE: f32 = 36.0;
+const PRESSURE_BAR_WIDTH: f32 = 100.0;
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Gaming), spawn_hud)
+            .add_systems(
+                Update,
+                (
+                    update_room_info,
+                    update_pressure_bar,
+                    update_item_bar,
+                    item_tooltip,
+                )
+                    .run_if(in_state(GameState::Gaming)),
+            );
+    }
+}
+
+#[derive(Component)]
+struct DepthText;
+
+#[derive(Component)]
+struct RoomText;
+
+#[derive(Component)]
+struct ObjectiveText;
+
+// 每个玩家一条压力条
+#[derive(Component)]
+struct PressureBarForeground(Player);
+
+#[derive(Component)]
+struct PressureText(Player);
+
+#[derive(Component)]
+struct ItemBar;
+
+// 道具栏里的图标，鼠标悬停时显示说明
+#[derive(Component)]
+struct ItemBarIcon(Item);
+
+#[derive(Component)]
+struct ItemTooltip;
+
+#[derive(Component)]
+struct ItemTooltipText;
+
+fn hud_text(font: &Handle<Font>, font_size: f32, color: Color) -> impl Bundle {
+    (
+        Text::default(),
+        TextFont {
+            font: font.clone(),
+            font_size,
+            ..default()
+        },
+        TextColor(color),
+        LocalizedFont,
+    )
+}
+
+fn spawn_hud(
+    mut commands: Commands,
+    play_mode: Res<PlayMode>,
+    texts: Res<Texts>,
+    assets: Res<GameAssets>,
+) {
+    let font = texts.font(&assets);
+
+    // 左下角：深度、房间、目标和压力条
+    commands
+        .spawn(Node {
+            left: Val::Px(20.0),
+            bottom: Val::Px(8.0),
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(4.0),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((hud_text(&font, 20.0, Color::WHITE), DepthText));
+            parent.spawn((hud_text(&font, 20.0, Color::WHITE), RoomText));
+            parent.spawn((
+                hud_text(&font, 18.0, Color::srgb(0.6, 0.85, 1.0)),
+                ObjectiveText,
+            ));
+            // 1P 在最下面，和冷却条的行对齐
+            for &player in play_mode.players().iter().rev() {
+                parent.spawn((hud_text(&font, 18.0, Color::WHITE), PressureText(player)));
+                parent
+                    .spawn((
+                        Node {
+                            width: Val::Px(PRESSURE_BAR_WIDTH),
+                            height: Val::Px(15.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.25, 0.25, 0.25)),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Node {
+                                width: Val::Percent(0.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            BackgroundColor(Color::WHITE),
+                            PressureBarForeground(player),
+                        ));
+                    });
+            }
+        });
+
+    // 右上角：已经收集的道具
+    commands.spawn((
+        Node {
+            top: Val::Px(10.0),
+            right: Val::Px(10.0),
+            max_width: Val::Px(ITEM_ICON_SIZE * 10.0),
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::RowReverse,
+            flex_wrap: FlexWrap::Wrap,
+            column_gap: Val::Px(4.0),
+            row_gap: Val::Px(4.0),
+            ..default()
+        },
+        ItemBar,
+    ));
+
+    commands
+        .spawn((
+            Node {
+                top: Val::Px(ITEM_ICON_SIZE * 2.0 + 24.0),
+                right: Val::Px(10.0),
+                max_width: Val::Px(300.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.85)),
+            Visibility::Hidden,
+            ItemTooltip,
+        ))
+        .with_children(|parent| {
+            parent.spawn((hud_text(&font, 18.0, Color::WHITE), ItemTooltipText));
+        });
+}
+
+fn room_key(room_type: RoomType) -> &'static str {
+    match room_type {
+        RoomType::Combat => "room.combat",
+        RoomType::Treasure => "room.treasure",
+        RoomType::Boss => "room.boss",
+        RoomType::PreBoss => "room.pre_boss",
+        RoomType::PostBoss => "room.post_boss",
+        RoomType::Start => "room.start",
+        RoomType::Store => "room.store",
+    }
+}
+
+// 只在内容变化时写入，避免每帧重新排版
+fn set_text(text: &mut Text, content: String) {
+    if text.0 != content {
+        text.0 = content;
+    }
+}
+
+fn update_room_info(
+    mut q_text: Query<
+        (&mut Text, Has<DepthText>, Has<RoomText>),
+        Or<(With<DepthText>, With<RoomText>, With<ObjectiveText>)>,
+    >,
+    brick_stats: Res<BrickStats>,
+    current_room: Res<CurrentRoom>,
+    choose_state: Res<State<ChooseState>>,
+    q_enemy: Query<(), With<Enemy>>,
+    texts: Res<Texts>,
+) {
+    let enemies = q_enemy.iter().count();
+    let objective = if enemies > 0 {
+        texts.format("hud.objective.enemies", &[("count", &enemies)])
+    } else if *choose_state.get() == ChooseState::Choosing {
+        texts.get("notify.room_cleared.description").to_string()
+    } else {
+        String::new()
+    };
+    for (mut text, is_depth, is_room) in q_text.iter_mut() {
+        let content = if is_depth {
+            texts.format("hud.depth", &[("depth", &brick_stats.current_room)])
+        } else if is_room {
+            texts.get(room_key(current_room.room_type)).to_string()
+        } else {
+            objective.clone()
+        };
+        set_text(&mut text, content);
+    }
+}
+
+fn update_pressure_bar(
+    q_brick: Query<(&Player, Ref<Pressure>), With<Brick>>,
+    mut q_foreground: Query<(&PressureBarForeground, &mut Node, &mut BackgroundColor)>,
+    mut q_text: Query<(&PressureText, &mut Text, &mut TextColor)>,
+    play_mode: Res<PlayMode>,
+    texts: Res<Texts>,
+) {
+    for (&player, pressure) in q_brick.iter() {
+        if !pressure.is_changed() && !texts.is_changed() {
+            continue;
+        }
+        let ratio = (pressure.current / pressure.max).clamp(0.0, 1.0);
+        // 颜色随压力从白变红
+        let color = Color::srgb(1.0, 1.0 - ratio, 1.0 - ratio);
+
+        for (foreground, mut node, mut background) in q_foreground.iter_mut() {
+            if foreground.0 == player {
+                node.width = Val::Percent(ratio * 100.0);
+                background.0 = color;
+            }
+        }
+
+        let mut content = texts.format(
+            "hud.pressure",
+            &[
+                ("current", &(pressure.current as i32)),
+                ("max", &(pressure.max as i32)),
+            ],
+        );
+        // 双人时在前面标出是哪个玩家
+        if *play_mode == PlayMode::Coop {
+            content = format!("{} {}", texts.get(player.key()), content);
+        }
+        for (pressure_text, mut text, mut text_color) in q_text.iter_mut() {
+            if pressure_text.0 == player {
+                text.0 = content.clone();
+                text_color.0 = color;
+            }
+        }
+    }
+}
+
+fn update_item_bar(
+    mut commands: Commands,
+    collection: Res<ItemCollection>,
+    registry: Res<ItemRegistry>,
+    item_bar: Single<(Entity, Option<&Children>), With<ItemBar>>,
+) {
+    // 只补上还没有图标的道具
+    let (item_bar, icons) = *item_bar;
+    let spawned = icons.map_or(0, |icons| icons.len());
+    for &item in collection.0.iter().skip(spawned) {
+        let icon = commands
+            .spawn((
+                Node {
+                    width: Val::Px(ITEM_ICON_SIZE),
+                    height: Val::Px(ITEM_ICON_SIZE),
+                    ..default()
+                },
+                ImageNode::new(registry.icon(item)).with_color(registry.get(item).tint_color()),
+                BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.6)),
+                Interaction::default(),
+                ItemBarIcon(item),
+            ))
+            .id();
+        commands.entity(item_bar).add_child(icon);
+    }
+}
+
+fn item_tooltip(
+    q_icon: Query<(&Interaction, &ItemBarIcon), Changed<Interaction>>,
+    tooltip: Single<&mut Visibility, With<ItemTooltip>>,
+    mut tooltip_text: Single<&mut Text, With<ItemTooltipText>>,
+    registry: Res<ItemRegistry>,
+    texts: Res<Texts>,
+) {
+    let mut visibility = tooltip.into_inner();
+    for (interaction, icon) in q_icon.iter() {
+        match interaction {
+            Interaction::Hovered | Interaction::Pressed => {
+                let def = registry.get(icon.0);
+                tooltip_text.0 =
+                    format!("{}\n{}", texts.get(&def.name), texts.get(&def.description));
+                *visibility = Visibility::Inherited;
+            }
+            Interaction::None => *visibility = Visibility::Hidden,
+        }
+    }
+}
@@ -5,17 +5,22 @@ use bevy::{
     render::render_resource::{AsBindGroup, ShaderRef},
 };
 
-use crate::{ball::Ball, brick::inventory::Inventory, GameAssets, GameState};
+use crate::{
+    ball::Ball,
+    brick::{inventory::Inventory, player::Player, Brick},
+    GameAssets, GameState,
+};
 
 pub struct InventoryPlugin;
 
 impl Plugin for InventoryPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(UiMaterialPlugin::<DottedBorderMaterial>::default())
-            .add_systems(OnEnter(GameState::Gaming), spawn_inventory)
             .add_systems(
                 Update,
-                (debug_inventory, update_inventory).run_if(in_state(GameState::Gaming)),
+                (spawn_inventory, update_inventory)
+                    .chain()
+                    .run_if(in_state(GameState::Gaming)),
             );
     }
 }
@@ -42,88 +47,80 @@ impl UiMaterial for DottedBorderMaterial {
     }
 }
 
+// 显示哪个砖块的背包
 #[derive(Component)]
-pub struct UiInventory;
+pub struct UiInventory(pub Entity);
 
 #[derive(Component)]
 pub struct UiInventoryChildNode;
 
+// 每个砖块一个背包栏，2P 的放在 1P 的上面
 fn spawn_inventory(
     mut commands: Commands,
     mut materials: ResMut<Assets<DottedBorderMaterial>>,
-    inventory: Res<Inventory>,
+    q_brick: Query<(Entity, &Player, &Inventory), Added<Brick>>,
     game_assets: Res<GameAssets>,
 ) {
-    let root = commands
-        .spawn((
-            Node {
-                bottom: Val::Px(1.0),
-                right: Val::Px(30.0),
-                position_type: PositionType::Absolute,
-                // max_width: Val::Px(500.0),
-                // max_height: Val::Px(60.0),
-                margin: UiRect::all(Val::Px(3.0)),
-                flex_direction: FlexDirection::RowReverse,
-                flex_wrap: FlexWrap::WrapReverse,
-                justify_content: JustifyContent::FlexEnd,
-                align_content: AlignContent::FlexEnd,
-                align_items: AlignItems::Center,
-                ..default()
-            },
-            BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.0)),
-            UiInventory,
-        ))
-        .id();
-    let virtul_child = commands.spawn(()).id();
-    commands.entity(root).add_child(virtul_child);
-
-    for i in 0..inventory.capacity() {
-        let child = commands
+    for (brick, player, inventory) in q_brick.iter() {
+        let root = commands
             .spawn((
                 Node {
-                    width: Val::Px(30.0),
-                    height: Val::Px(30.0),
+                    bottom: Val::Px(1.0 + 40.0 * player.index() as f32),
+                    right: Val::Px(30.0),
+                    position_type: PositionType::Absolute,
+                    // max_width: Val::Px(500.0),
+                    // max_height: Val::Px(60.0),
+                    margin: UiRect::all(Val::Px(3.0)),
+                    flex_direction: FlexDirection::RowReverse,
+                    flex_wrap: FlexWrap::WrapReverse,
+                    justify_content: JustifyContent::FlexEnd,
+                    align_content: AlignContent::FlexEnd,
+                    align_items: AlignItems::Center,
                     ..default()
                 },
-                MaterialNode(materials.add(DottedBorderMaterial {
-                    border_color: Vec4::new(0.0, 0.0, 0.0, 1.0), // 黑色边框
-                    dot_radius: 0.3,                             // 圆点大小（比例）
-                    dot_spacing: 0.1,                            // 圆点间距（比例）
-                    border_width: 0.05,                          // 边框宽度（占 UI 节点比例）
-                    inner_texture: match_ball(inventory.get(i), &game_assets),
-                })),
-                UiInventoryChildNode,
+                BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.0)),
+                UiInventory(brick),
             ))
             .id();
-        commands.entity(root).add_child(child);
-    }
-}
+        let virtul_child = commands.spawn(()).id();
+        commands.entity(root).add_child(virtul_child);
 
-fn debug_inventory(mut inventory: ResMut<Inventory>, key: Res<ButtonInput<KeyCode>>) {
-    if key.just_pressed(KeyCode::KeyP) {
-        let _ = inventory.push(Ball::Tennis);
-    }
-    if key.just_pressed(KeyCode::KeyO) {
-        inventory.expansion(1);
-    }
-    if key.just_pressed(KeyCode::KeyI) {
-        inventory.pop();
-    }
-    if key.just_pressed(KeyCode::KeyU) {
-        inventory.redusing(1);
+        for i in 0..inventory.capacity() {
+            let child = commands
+                .spawn((
+                    Node {
+                        width: Val::Px(30.0),
+                        height: Val::Px(30.0),
+                        ..default()
+                    },
+                    MaterialNode(materials.add(DottedBorderMaterial {
+                        border_color: Vec4::new(0.0, 0.0, 0.0, 1.0), // 黑色边框
+                        dot_radius: 0.3,                             // 圆点大小（比例）
+                        dot_spacing: 0.1,                            // 圆点间距（比例）
+                        border_width: 0.05,                          // 边框宽度（占 UI 节点比例）
+                        inner_texture: match_ball(inventory.get(i), &game_assets),
+                    })),
+                    UiInventoryChildNode,
+                ))
+                .id();
+            commands.entity(root).add_child(child);
+        }
     }
 }
 
 fn update_inventory(
     mut commands: Commands,
-    inventory: Res<Inventory>,
+    q_inventory: Query<Ref<Inventory>, With<Brick>>,
     mut materials: ResMut<Assets<DottedBorderMaterial>>,
     q_mat: Query<&MaterialNode<DottedBorderMaterial>>,
     game_assets: Res<GameAssets>,
-    q_ui_inventory: Query<(&Children, Entity), With<UiInventory>>,
+    q_ui_inventory: Query<(&Children, Entity, &UiInventory)>,
 ) {
-    if inventory.is_changed() {
-        for (children, entity) in q_ui_inventory.iter() {
+    for (children, entity, ui_inventory) in q_ui_inventory.iter() {
+        let Ok(inventory) = q_inventory.get(ui_inventory.0) else {
+            continue;
+        };
+        if inventory.is_changed() {
             let children_count = children.len() - 1;
             let diff: i32 = inventory.capacity() as i32 - children_count as i32;
             // println!("diff:{}", diff);
@@ -133,7 +130,7 @@ fn update_inventory(
             //     children_count
             // );
             if diff > 0 {
-                for _ in 0..diff {
+                for i in 0..diff as usize {
                     let child = commands
                         .spawn((
                             Node {
@@ -146,7 +143,10 @@ fn update_inventory(
                                 dot_radius: 0.3,                             // 圆点大小（比例）
                                 dot_spacing: 0.1,                            // 圆点间距（比例）
                                 border_width: 0.05, // 边框宽度（占 UI 节点比例）
-                                inner_texture: match_ball(None, &game_assets),
+                                inner_texture: match_ball(
+                                    inventory.get(children_count + i),
+                                    &game_assets,
+                                ),
                             })),
                             UiInventoryChildNode,
                         ))
@@ -162,7 +162,9 @@ fn update_inventory(
                     }
                 }
             }
-            for (index, handle) in q_mat.iter().enumerate() {
+            // 第一个子节点是占位的空节点，没有材质
+            let slots = children.iter().filter_map(|child| q_mat.get(*child).ok());
+            for (index, handle) in slots.enumerate() {
                 if let Some(materia) = materials.get_mut(handle) {
                     materia.inner_texture = match_ball(inventory.get(index), &game_assets);
                 }
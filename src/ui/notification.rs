@@ -0,0 +1,398 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    color::palettes::css::{CRIMSON, WHITE},
+    prelude::*,
+    utils::HashMap,
+};
+
+use crate::{
+    audio::DuckMusicEvent,
+    brick::{
+        input::KeyBindings,
+        player::{PlayMode, Player},
+        stats::Pressure,
+        Brick,
+    },
+    localization::{LocalizedFont, Texts},
+    world::map::room::{
+        control::ChooseState,
+        loading::{CurrentRoom, LoadingState},
+        select::SelectedRooms,
+        RoomType,
+    },
+    GameAssets, GameState,
+};
+
+// 同时显示的通知数量，多出来的排队
+const MAX_VISIBLE: usize = 3;
+const FADE_SECONDS: f32 = 0.3;
+// 压力比例超过这个值时发出警告
+const PRESSURE_WARNING: f32 = 0.8;
+
+pub struct NotificationPlugin;
+
+impl Plugin for NotificationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<NotifyEvent>()
+            .init_resource::<NotificationQueue>()
+            .add_systems(OnEnter(GameState::Gaming), setup_notification_area)
+            .add_systems(OnExit(GameState::Gaming), clear_queue)
+            .add_systems(OnEnter(LoadingState::FadeIn), notify_room_objective)
+            .add_systems(OnEnter(ChooseState::Choosing), notify_room_cleared)
+            .add_systems(
+                Update,
+                (
+                    pressure_warning,
+                    queue_notifications,
+                    skip_notification,
+                    show_notifications,
+                    animate_notifications,
+                    fade_notification_parts,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Gaming)),
+            );
+    }
+}
+
+// 通知的种类，决定背景颜色和显示时间
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NotificationKind {
+    Item,
+    // 道具组合解锁
+    Synergy,
+    // 房间目标
+    Objective,
+    Warning,
+}
+
+impl NotificationKind {
+    fn seconds(&self) -> f32 {
+        match self {
+            NotificationKind::Item | NotificationKind::Synergy => 2.0,
+            NotificationKind::Objective => 1.6,
+            NotificationKind::Warning => 1.2,
+        }
+    }
+
+    fn background(&self) -> Color {
+        match self {
+            NotificationKind::Item => Color::srgba(0.25, 0.25, 0.25, 0.9),
+            NotificationKind::Synergy => Color::srgba(0.2, 0.1, 0.3, 0.9),
+            NotificationKind::Objective => Color::srgba(0.1, 0.2, 0.3, 0.9),
+            NotificationKind::Warning => Color::srgba(0.35, 0.05, 0.05, 0.9),
+        }
+    }
+
+    // 拾取道具时压低音乐
+    fn ducks_music(&self) -> bool {
+        matches!(self, NotificationKind::Item | NotificationKind::Synergy)
+    }
+}
+
+#[derive(Clone)]
+pub struct Notification {
+    pub kind: NotificationKind,
+    pub title: String,
+    pub description: String,
+    pub icon: Option<ImageNode>,
+    // 标题和边框的颜色，道具按稀有度上色
+    pub color: Color,
+}
+
+#[derive(Event)]
+pub struct NotifyEvent(pub Notification);
+
+#[derive(Resource, Default)]
+struct NotificationQueue(VecDeque<Notification>);
+
+// 所有通知从上往下排列的容器
+#[derive(Component)]
+struct NotificationArea;
+
+#[derive(Component)]
+struct NotificationCard {
+    timer: Timer,
+    alpha: f32,
+}
+
+impl NotificationCard {
+    fn new(kind: NotificationKind) -> Self {
+        Self {
+            timer: Timer::from_seconds(kind.seconds() + FADE_SECONDS * 2.0, TimerMode::Once),
+            alpha: 0.0,
+        }
+    }
+}
+
+// 跟着通知一起淡入淡出的部分，记录原本的透明度
+#[derive(Component)]
+struct FadeWith {
+    card: Entity,
+    alpha: f32,
+}
+
+fn setup_notification_area(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            top: Val::Px(20.0),
+            position_type: PositionType::Absolute,
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(8.0),
+            ..default()
+        },
+        NotificationArea,
+    ));
+}
+
+fn clear_queue(mut queue: ResMut<NotificationQueue>) {
+    queue.0.clear();
+}
+
+fn queue_notifications(mut events: EventReader<NotifyEvent>, mut queue: ResMut<NotificationQueue>) {
+    for event in events.read() {
+        queue.0.push_back(event.0.clone());
+    }
+}
+
+// 按键让最早的通知立刻开始消失，不影响其他按键
+fn skip_notification(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    area: Single<&Children, With<NotificationArea>>,
+    mut q_card: Query<&mut NotificationCard>,
+) {
+    if !keys.just_pressed(bindings.skip_notification) {
+        return;
+    }
+    // 已经在淡出的通知不算
+    let oldest = area.iter().copied().find(|&entity| {
+        q_card
+            .get(entity)
+            .is_ok_and(|card| card.timer.remaining_secs() > FADE_SECONDS)
+    });
+    let Some(mut card) = oldest.and_then(|entity| q_card.get_mut(entity).ok()) else {
+        return;
+    };
+    let duration = card.timer.duration();
+    let skip_to = duration.saturating_sub(std::time::Duration::from_secs_f32(FADE_SECONDS));
+    card.timer.set_elapsed(skip_to);
+}
+
+fn show_notifications(
+    mut commands: Commands,
+    mut queue: ResMut<NotificationQueue>,
+    area: Single<(Entity, Option<&Children>), With<NotificationArea>>,
+    mut duck_events: EventWriter<DuckMusicEvent>,
+    texts: Res<Texts>,
+    assets: Res<GameAssets>,
+) {
+    let (area, children) = *area;
+    let visible = children.map_or(0, |children| children.len());
+    for _ in visible..MAX_VISIBLE {
+        let Some(notification) = queue.0.pop_front() else {
+            return;
+        };
+        if notification.kind.ducks_music() {
+            duck_events.send(DuckMusicEvent {
+                volume: 0.4,
+                seconds: notification.kind.seconds() + FADE_SECONDS * 2.0,
+            });
+        }
+        let card = spawn_card(&mut commands, notification, texts.font(&assets));
+        commands.entity(area).add_child(card);
+    }
+}
+
+// 一条通知：左边图标，右边标题和描述
+fn spawn_card(commands: &mut Commands, notification: Notification, font: Handle<Font>) -> Entity {
+    let background = notification.kind.background();
+    let card = commands
+        .spawn((
+            Node {
+                width: Val::Px(520.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                border: UiRect::all(Val::Px(2.0)),
+                column_gap: Val::Px(12.0),
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(background.with_alpha(0.0)),
+            BorderColor(notification.color.with_alpha(0.0)),
+            NotificationCard::new(notification.kind),
+        ))
+        .id();
+    commands.entity(card).insert(FadeWith {
+        card,
+        alpha: background.alpha(),
+    });
+
+    commands.entity(card).with_children(|parent| {
+        if let Some(icon) = notification.icon {
+            let alpha = icon.color.alpha();
+            parent.spawn((
+                Node {
+                    width: Val::Px(48.0),
+                    height: Val::Px(48.0),
+                    ..default()
+                },
+                icon,
+                FadeWith { card, alpha },
+            ));
+        }
+        parent
+            .spawn(Node {
+                flex_direction: FlexDirection::Column,
+                flex_grow: 1.0,
+                ..default()
+            })
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new(notification.title),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 28.0,
+                        ..default()
+                    },
+                    TextColor(notification.color),
+                    LocalizedFont,
+                    FadeWith { card, alpha: 1.0 },
+                ));
+                if !notification.description.is_empty() {
+                    parent.spawn((
+                        Text::new(notification.description),
+                        TextFont {
+                            font,
+                            font_size: 20.0,
+                            ..default()
+                        },
+                        TextColor(WHITE.into()),
+                        LocalizedFont,
+                        FadeWith { card, alpha: 1.0 },
+                    ));
+                }
+            });
+    });
+    card
+}
+
+// 淡入、停留、淡出，结束后移除
+fn animate_notifications(
+    mut commands: Commands,
+    mut q_card: Query<(Entity, &mut NotificationCard)>,
+    time: Res<Time>,
+) {
+    for (entity, mut card) in q_card.iter_mut() {
+        card.timer.tick(time.delta());
+        if card.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        let elapsed = card.timer.elapsed_secs();
+        let remaining = card.timer.remaining_secs();
+        card.alpha = (elapsed.min(remaining) / FADE_SECONDS).min(1.0);
+    }
+}
+
+fn fade_notification_parts(
+    q_card: Query<&NotificationCard>,
+    mut q_part: Query<(
+        &FadeWith,
+        Option<&mut BackgroundColor>,
+        Option<&mut BorderColor>,
+        Option<&mut TextColor>,
+        Option<&mut ImageNode>,
+    )>,
+) {
+    for (fade, background, border, text, image) in q_part.iter_mut() {
+        let Ok(card) = q_card.get(fade.card) else {
+            continue;
+        };
+        let alpha = fade.alpha * card.alpha;
+        if let Some(mut background) = background {
+            background.0.set_alpha(alpha);
+        }
+        if let Some(mut border) = border {
+            border.0.set_alpha(card.alpha);
+        }
+        if let Some(mut text) = text {
+            text.0.set_alpha(alpha);
+        }
+        if let Some(mut image) = image {
+            image.color.set_alpha(alpha);
+        }
+    }
+}
+
+// 进入房间时提示这个房间的目标
+fn notify_room_objective(
+    selected_rooms: Res<SelectedRooms>,
+    mut events: EventWriter<NotifyEvent>,
+    texts: Res<Texts>,
+) {
+    let Some(room) = selected_rooms.rooms.get(selected_rooms.index) else {
+        return;
+    };
+    let key = match room.room_type {
+        RoomType::Boss => "notify.objective.boss",
+        RoomType::Treasure => "notify.objective.treasure",
+        RoomType::Store => "notify.objective.store",
+        _ if room.encounter.is_some() => "notify.objective.combat",
+        _ => return,
+    };
+    events.send(NotifyEvent(Notification {
+        kind: NotificationKind::Objective,
+        title: texts.get(key).into(),
+        description: String::new(),
+        icon: None,
+        color: Color::srgb(0.6, 0.85, 1.0),
+    }));
+}
+
+// 需要消灭敌人的房间清空后提示选择出口
+fn notify_room_cleared(
+    current_room: Res<CurrentRoom>,
+    mut events: EventWriter<NotifyEvent>,
+    texts: Res<Texts>,
+) {
+    if !current_room.has_encounter {
+        return;
+    }
+    events.send(NotifyEvent(Notification {
+        kind: NotificationKind::Objective,
+        title: texts.get("notify.room_cleared").into(),
+        description: texts.get("notify.room_cleared.description").into(),
+        icon: None,
+        color: Color::srgb(0.6, 0.85, 1.0),
+    }));
+}
+
+// 压力从下往上越过警戒线时提醒一次，双人时标题前面标出玩家
+fn pressure_warning(
+    q_pressure: Query<(Entity, &Player, &Pressure), (With<Brick>, Changed<Pressure>)>,
+    mut last_ratios: Local<HashMap<Entity, f32>>,
+    mut events: EventWriter<NotifyEvent>,
+    play_mode: Res<PlayMode>,
+    texts: Res<Texts>,
+) {
+    for (brick, player, pressure) in q_pressure.iter() {
+        let ratio = pressure.current / pressure.max;
+        let last_ratio = last_ratios.insert(brick, ratio).unwrap_or(0.0);
+        if last_ratio < PRESSURE_WARNING && ratio >= PRESSURE_WARNING {
+            let mut title = texts.get("notify.high_pressure").to_string();
+            if *play_mode == PlayMode::Coop {
+                title = format!("{} {}", texts.get(player.key()), title);
+            }
+            events.send(NotifyEvent(Notification {
+                kind: NotificationKind::Warning,
+                title,
+                description: texts.get("notify.high_pressure.description").into(),
+                icon: None,
+                color: CRIMSON.into(),
+            }));
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use bevy::{prelude::*, sprite::Anchor};
+
+use crate::{
+    brick::{
+        input::KeyBindings,
+        player::{PlayMode, Player},
+        stats::{BaseStats, ModifierOp, Stat, StatModifiers},
+        Brick,
+    },
+    item::ItemRegistry,
+    localization::{LocalizedFont, Texts},
+    GameAssets, GameState, WINDOW_HEIGHT, WINDOW_WIDTH,
+};
+
+const PANEL_SIZE: Vec2 = Vec2::new(260.0, 320.0);
+
+#[derive(Component)]
+struct StatsPanel;
+
+#[derive(Component)]
+struct StatsPanelText;
+
+pub struct StatsPanelPlugin;
+
+impl Plugin for StatsPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Gaming), spawn_stats_panel)
+            .add_systems(
+                Update,
+                (toggle_stats_panel, update_stats_panel).run_if(in_state(GameState::Gaming)),
+            );
+    }
+}
+
+fn spawn_stats_panel(mut commands: Commands, assets: Res<GameAssets>) {
+    commands
+        .spawn((
+            Sprite::from_color(Color::srgba(0.1, 0.1, 0.1, 0.85), PANEL_SIZE),
+            Transform::from_xyz(
+                -WINDOW_WIDTH / 2.0 + PANEL_SIZE.x / 2.0 + 10.0,
+                WINDOW_HEIGHT / 2.0 - PANEL_SIZE.y / 2.0 - 10.0,
+                6.0,
+            ),
+            Visibility::Hidden,
+            StatsPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text2d::new(""),
+                TextFont {
+                    font: assets.font.clone(),
+                    font_size: 18.0,
+                    ..default()
+                },
+                TextLayout::new_with_justify(JustifyText::Left),
+                TextColor(Color::WHITE),
+                // 文字从面板左上角开始排列
+                Anchor::TopLeft,
+                Transform::from_xyz(-PANEL_SIZE.x / 2.0 + 12.0, PANEL_SIZE.y / 2.0 - 12.0, 1.0),
+                StatsPanelText,
+                LocalizedFont,
+            ));
+        });
+}
+
+fn toggle_stats_panel(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<KeyBindings>,
+    mut panel_visibility: Single<&mut Visibility, With<StatsPanel>>,
+) {
+    if keys.just_pressed(bindings.stats_panel) {
+        panel_visibility.toggle_visible_hidden();
+    }
+}
+
+// 列出每个属性的最终值和带来修正的道具，切换语言时也要刷新；双人时按玩家分开列出
+fn update_stats_panel(
+    q_brick: Query<(&Player, &BaseStats, Ref<StatModifiers>), With<Brick>>,
+    mut panel_text: Single<&mut Text2d, With<StatsPanelText>>,
+    play_mode: Res<PlayMode>,
+    registry: Res<ItemRegistry>,
+    texts: Res<Texts>,
+) {
+    let changed = q_brick
+        .iter()
+        .any(|(_, _, modifiers)| modifiers.is_changed());
+    if !changed && !texts.is_changed() {
+        return;
+    }
+    let mut bricks: Vec<_> = q_brick.iter().collect();
+    bricks.sort_by_key(|(&player, ..)| player);
+    let mut text = format!("{}\n", texts.get("stats.title"));
+    for (player, base, modifiers) in bricks {
+        if *play_mode == PlayMode::Coop {
+            text.push_str(&format!("[{}]\n", texts.get(player.key())));
+        }
+        push_stats(&mut text, base, &modifiers, &registry, &texts);
+    }
+    panel_text.0 = text;
+}
+
+fn push_stats(
+    text: &mut String,
+    base: &BaseStats,
+    modifiers: &StatModifiers,
+    registry: &ItemRegistry,
+    texts: &Texts,
+) {
+    for stat in Stat::ALL {
+        text.push_str(&format!(
+            "{}: {:.1}\n",
+            texts.get(stat.key()),
+            modifiers.value(base, stat)
+        ));
+        for modifier in modifiers.iter().filter(|modifier| modifier.stat == stat) {
+            let op = match modifier.op {
+                ModifierOp::Add(value) => format!("{:+}", value),
+                ModifierOp::Multiply(value) => format!("x{}", value),
+            };
+            text.push_str(&format!(
+                "    {} {}\n",
+                texts.get(registry.source_name(modifier.source)),
+                op
+            ));
+        }
+    }
+}
@@ -1,5 +1,4 @@
-mod camera;
-mod enemy_spawner;
+pub mod camera;
 pub mod map;
 
 use bevy::prelude::*;
@@ -8,10 +7,6 @@ pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((
-            camera::CameraPlugin,
-            map::MapPlugin,
-            enemy_spawner::EnemySpawnerPlugin,
-        ));
+        app.add_plugins((camera::CameraPlugin, map::MapPlugin));
     }
 }
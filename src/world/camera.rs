@@ -1,4 +1,11 @@
-use bevy::prelude::*;
+use bevy::{
+    prelude::*,
+    render::camera::{ScalingMode, Viewport},
+    window::{PrimaryWindow, WindowResized},
+};
+use bevy_kira_audio::prelude::SpatialAudioReceiver;
+
+use crate::{WINDOW_HEIGHT, WINDOW_WIDTH};
 
 #[derive(Component)]
 pub struct MainCamera;
@@ -7,10 +14,56 @@ pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn_camera);
+        app.add_systems(Startup, spawn_camera)
+            .add_systems(PreUpdate, fit_viewport_to_window);
     }
 }
 
+// 游戏世界固定为 1280x720 的虚拟分辨率，窗口比例不同时在两侧或上下留黑边
 fn spawn_camera(mut commands: Commands) {
-    commands.spawn((MainCamera, Camera2d, Msaa::Off));
+    commands.spawn((
+        MainCamera,
+        Camera2d,
+        OrthographicProjection {
+            scaling_mode: ScalingMode::Fixed {
+                width: WINDOW_WIDTH,
+                height: WINDOW_HEIGHT,
+            },
+            ..OrthographicProjection::default_2d()
+        },
+        IsDefaultUiCamera,
+        Msaa::Off,
+        SpatialAudioReceiver,
+    ));
+}
+
+// 窗口大小变化时重新计算视口，界面也按视口等比缩放
+fn fit_viewport_to_window(
+    mut resized_events: EventReader<WindowResized>,
+    window: Single<&Window, With<PrimaryWindow>>,
+    mut camera: Single<&mut Camera, With<MainCamera>>,
+    mut ui_scale: ResMut<UiScale>,
+    mut initialized: Local<bool>,
+) {
+    if resized_events.read().count() == 0 && *initialized {
+        return;
+    }
+    let window_size = window.physical_size().as_vec2();
+    let scale = (window_size.x / WINDOW_WIDTH).min(window_size.y / WINDOW_HEIGHT);
+    if scale <= 0.0 {
+        return;
+    }
+    *initialized = true;
+
+    let size = (Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT) * scale)
+        .round()
+        .max(Vec2::ONE);
+    let position = ((window_size - size) / 2.0).max(Vec2::ZERO).round();
+    camera.viewport = Some(Viewport {
+        physical_position: position.as_uvec2(),
+        physical_size: size.as_uvec2(),
+        ..default()
+    });
+    // UiScale 作用于逻辑像素，需要去掉系统的缩放倍率
+    ui_scale.0 = scale / window.scale_factor();
 }
@@ -2,6 +2,7 @@ pub mod pipelines_readdy;
 pub mod room;
 
 use bevy::prelude::*;
+use bevy::utils::HashMap;
 use bevy_rapier2d::prelude::*;
 
 use bevy_rapier2d::rapier::prelude::CollisionEventFlags;
@@ -11,16 +12,16 @@ use room::loading::{LoadingData, LoadingState, RoomComponents};
 use room::select::SelectedRooms;
 
 use crate::audio::PlaySound;
-use crate::audio::Volume;
 use crate::ball::Ball;
-use crate::brick::{stats::BrickStats, Brick};
+use crate::brick::{player::Player, stats::BrickStats, Brick, ShotBy};
 use crate::collision_group::*;
 use crate::enemy::envy::SpawnEnvyEvent;
 use crate::enemy::gluttony::SpawnGluttonyEvent;
 use crate::enemy::greed::SpawnGreedEvent;
 use crate::enemy::sloth::SpawnSlothEvent;
-use crate::events::{Damage, DamageEvent};
-use crate::item::sapwn::SpawnItemEvent;
+use crate::events::{BallLostEvent, Damage, DamageEvent, DamageKind};
+use crate::item::effects::{Shield, SplitBall};
+use crate::item::{sapwn::SpawnItemEvent, PoolKind};
 use crate::utils::anim_sprite::{AnimSprite, AnimSpriteTimer, AnimationIndices};
 use crate::{GameAssets, GameState};
 use crate::{WINDOW_HEIGHT, WINDOW_WIDTH};
@@ -40,7 +41,11 @@ impl Plugin for MapPlugin {
                     .run_if(in_state(ChooseState::Choosing))
                     .run_if(in_state(GameState::Gaming)),
             )
-            .add_systems(OnEnter(ChooseState::Choosing), active_sensor_door)
+            .init_resource::<DoorVotes>()
+            .add_systems(
+                OnEnter(ChooseState::Choosing),
+                (active_sensor_door, clear_door_votes),
+            )
             .add_plugins((room::RoomPlugin,));
     }
 }
@@ -57,6 +62,10 @@ pub struct SensorDoor;
 #[derive(Component)]
 pub struct Index(pub usize);
 
+// 每个砖块当前站在哪扇门里
+#[derive(Resource, Default)]
+struct DoorVotes(HashMap<Entity, Entity>);
+
 fn spawn_map(
     mut commands: Commands,
     assets: Res<GameAssets>,
@@ -72,6 +81,7 @@ fn spawn_map(
     });
     spawn_item_event_writer.send(SpawnItemEvent {
         pos: Vec2::new(60.0, 60.0),
+        pool: PoolKind::Treasure,
     });
 
     let arena_sheet = assets.arena01_texture.clone();
@@ -267,42 +277,72 @@ fn spawn_map(
     commands.entity(r_d_entity).add_child(r_d_collider);
 }
 
+// 漏球的伤害算在把球打出去的砖块头上，找不到时算给 1P
 fn handle_sensor_wall(
     // mut commands: Commands,
     mut collision_events: EventReader<CollisionEvent>,
     mut enents: EventWriter<DamageEvent>,
-    brick_entity: Single<Entity, With<Brick>>,
+    mut ball_lost_events: EventWriter<BallLostEvent>,
+    mut q_brick: Query<(Entity, &Player, &mut Shield), With<Brick>>,
     sensor_query: Query<(), (With<Sensor>, With<SensorWall>)>,
     q_ball: Query<(), With<Ball>>,
+    q_split_ball: Query<(), With<SplitBall>>,
+    q_shot_by: Query<&ShotBy>,
     q_damage: Query<&Damage>,
     q_parent: Query<&Parent>,
 ) {
     for event in collision_events.read() {
         if let CollisionEvent::Started(entity_a, entity_b, _) = event {
-            if sensor_query.contains(*entity_b) {
-                let parent_entity = q_parent.get(*entity_a).ok().unwrap().get();
-                enents.send(DamageEvent {
-                    offender: *entity_a,
-                    victim: *brick_entity,
-                    damage: *q_damage.get(parent_entity).ok().unwrap(),
-                });
-                // commands.entity(parent_entity).despawn_recursive();
-                println!("despawn ball");
+            let ball_collider = if sensor_query.contains(*entity_b) {
+                *entity_a
+            } else if sensor_query.contains(*entity_a) {
+                *entity_b
+            } else {
+                continue;
+            };
+            let parent_entity = q_parent.get(ball_collider).ok().unwrap().get();
+            ball_lost_events.send(BallLostEvent {
+                ball: parent_entity,
+            });
+            // 分裂出来的球漏掉不受伤害，护盾可以抵挡一次漏球
+            if q_split_ball.contains(parent_entity) {
+                continue;
             }
-            if sensor_query.contains(*entity_a) {
-                let parent_entity = q_parent.get(*entity_b).ok().unwrap().get();
-                enents.send(DamageEvent {
-                    offender: *entity_b,
-                    victim: *brick_entity,
-                    damage: *q_damage.get(parent_entity).ok().unwrap(),
+            let shooter = q_shot_by
+                .get(parent_entity)
+                .ok()
+                .map(|shot_by| shot_by.0)
+                .filter(|&shooter| q_brick.contains(shooter))
+                .or_else(|| {
+                    q_brick
+                        .iter()
+                        .find(|(_, &player, _)| player == Player::One)
+                        .map(|(brick, ..)| brick)
                 });
-                // commands.entity(parent_entity).despawn_recursive();
-                println!("despawn ball");
+            let Some(Ok((brick_entity, _, mut shield))) = shooter.map(|b| q_brick.get_mut(b))
+            else {
+                continue;
+            };
+            if shield.charges > 0 {
+                shield.charges -= 1;
+                continue;
             }
+            enents.send(DamageEvent {
+                offender: ball_collider,
+                victim: brick_entity,
+                damage: *q_damage.get(parent_entity).ok().unwrap(),
+                kind: DamageKind::BallLost,
+            });
+            // commands.entity(parent_entity).despawn_recursive();
+            println!("despawn ball");
         }
     }
 }
 
+fn clear_door_votes(mut votes: ResMut<DoorVotes>) {
+    votes.0.clear();
+}
+
 fn active_sensor_door(mut commands: Commands, q_door: Query<Entity, With<SensorDoor>>) {
     for entity in q_door.iter() {
         commands
@@ -311,54 +351,61 @@ fn active_sensor_door(mut commands: Commands, q_door: Query<Entity, With<SensorD
     }
 }
 
+// 双人时两个砖块都站进同一扇门才进入下一个房间
 fn handle_sensor_door(
     mut collision_events: EventReader<CollisionEvent>,
     q_sensor: Query<(), (With<Sensor>, With<SensorDoor>)>,
-    q_brick: Query<(), With<Brick>>,
+    q_brick: Query<Entity, With<Brick>>,
     q_index: Query<&Index>,
     q_parent: Query<&Parent>,
+    mut votes: ResMut<DoorVotes>,
     mut next_choose_state: ResMut<NextState<ChooseState>>,
     mut next_loading_state: ResMut<NextState<LoadingState>>,
     mut selected_rooms: ResMut<SelectedRooms>,
     mut sound_events: EventWriter<PlaySound>,
     game_assets: Res<GameAssets>,
-    volume: Res<Volume>,
 ) {
     for event in collision_events.read() {
-        if let CollisionEvent::Started(entity_a, entity_b, CollisionEventFlags::SENSOR) = event {
-            if q_sensor.contains(*entity_a) {
-                // a 为 door b other
-                if let Ok(parent) = q_parent.get(*entity_b) {
-                    let parent_entity = parent.get();
-                    if q_brick.contains(parent_entity) {}
-                    let index = q_index.get(*entity_a).unwrap();
-                    selected_rooms.index = index.0;
-                    next_choose_state.set(ChooseState::Ready);
-                    next_loading_state.set(LoadingState::FadeOut);
-                    sound_events.send(PlaySound {
-                        clip: game_assets.close_door_sound.clone(),
-                        volume: volume.0 as f64,
-                        ..default()
-                    });
-                }
-            }
-            if q_sensor.contains(*entity_b) {
-                // b 为 door a other
-                if let Ok(parent) = q_parent.get(*entity_a) {
-                    let parent_entity = parent.get();
-                    if q_brick.contains(parent_entity) {
-                        let index = q_index.get(*entity_b).unwrap();
-                        selected_rooms.index = index.0;
-                        next_choose_state.set(ChooseState::Ready);
-                        next_loading_state.set(LoadingState::FadeOut);
-                        sound_events.send(PlaySound {
-                            clip: game_assets.close_door_sound.clone(),
-                            volume: volume.0 as f64,
-                            ..default()
-                        });
-                    }
-                }
-            }
+        let (entity_a, entity_b, entered) = match event {
+            CollisionEvent::Started(a, b, CollisionEventFlags::SENSOR) => (a, b, true),
+            CollisionEvent::Stopped(a, b, CollisionEventFlags::SENSOR) => (a, b, false),
+            _ => continue,
+        };
+        // 找出门和另一边的砖块
+        let (door, other) = if q_sensor.contains(*entity_a) {
+            (*entity_a, *entity_b)
+        } else if q_sensor.contains(*entity_b) {
+            (*entity_b, *entity_a)
+        } else {
+            continue;
+        };
+        let Ok(parent) = q_parent.get(other) else {
+            continue;
+        };
+        let brick = parent.get();
+        if !q_brick.contains(brick) {
+            continue;
+        }
+        if entered {
+            votes.0.insert(brick, door);
+        } else if votes.0.get(&brick) == Some(&door) {
+            votes.0.remove(&brick);
         }
     }
+
+    let mut bricks = q_brick.iter();
+    let Some(first) = bricks.next().and_then(|brick| votes.0.get(&brick).copied()) else {
+        return;
+    };
+    if !bricks.all(|brick| votes.0.get(&brick) == Some(&first)) {
+        return;
+    }
+    votes.0.clear();
+    selected_rooms.index = q_index.get(first).unwrap().0;
+    next_choose_state.set(ChooseState::Ready);
+    next_loading_state.set(LoadingState::FadeOut);
+    sound_events.send(PlaySound {
+        clip: game_assets.close_door_sound.clone(),
+        ..default()
+    });
 }
@@ -11,7 +11,7 @@ use serde::Deserialize;
 
 use crate::enemy::Enemy;
 
-#[derive(Deserialize, Debug, Clone, Copy)]
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum RoomType {
     Combat,
     Treasure,
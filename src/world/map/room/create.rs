@@ -7,7 +7,7 @@ use crate::{
         GROUP_WALL,
     },
     utils::anim_sprite::{AnimSprite, AnimSpriteTimer, AnimationIndices},
-    world::map::{Index, SensorDoor, Wall},
+    world::map::{Index, SensorDoor, SensorWall, Wall},
     GameAssets, GameState, WINDOW_HEIGHT, WINDOW_WIDTH,
 };
 
@@ -146,6 +146,7 @@ pub fn boss(
             Collider::cuboid(WINDOW_WIDTH / 2.0, wall_thickness),
             Sensor,
             CollisionGroups::new(GROUP_SENSOR_DEAD_ZONE, GROUP_BALL),
+            SensorWall,
         ))
         .id();
 
@@ -326,6 +327,7 @@ pub fn combat(
             Collider::cuboid(WINDOW_WIDTH / 2.0, wall_thickness),
             Sensor,
             CollisionGroups::new(GROUP_SENSOR_DEAD_ZONE, GROUP_BALL),
+            SensorWall,
         ))
         .id();
     commands.entity(s_entity).insert_children(0, &[s_collider]);
@@ -506,6 +508,7 @@ pub fn post_boss(
             Collider::cuboid(WINDOW_WIDTH / 2.0, wall_thickness),
             Sensor,
             CollisionGroups::new(GROUP_SENSOR_DEAD_ZONE, GROUP_BALL),
+            SensorWall,
         ))
         .id();
     commands.entity(s_entity).insert_children(0, &[s_collider]);
@@ -685,6 +688,7 @@ pub fn pre_boss(
             Collider::cuboid(WINDOW_WIDTH / 2.0, wall_thickness),
             Sensor,
             CollisionGroups::new(GROUP_SENSOR_DEAD_ZONE, GROUP_BALL),
+            SensorWall,
         ))
         .id();
     commands.entity(s_entity).insert_children(0, &[s_collider]);
@@ -864,6 +868,7 @@ pub fn start(
             Collider::cuboid(WINDOW_WIDTH / 2.0, wall_thickness),
             Sensor,
             CollisionGroups::new(GROUP_SENSOR_DEAD_ZONE, GROUP_BALL),
+            SensorWall,
         ))
         .id();
     commands.entity(s_entity).insert_children(0, &[s_collider]);
@@ -1043,6 +1048,7 @@ pub fn treasure(
             Collider::cuboid(WINDOW_WIDTH / 2.0, wall_thickness),
             Sensor,
             CollisionGroups::new(GROUP_SENSOR_DEAD_ZONE, GROUP_BALL),
+            SensorWall,
         ))
         .id();
     commands.entity(s_entity).insert_children(0, &[s_collider]);
@@ -1222,6 +1228,7 @@ pub fn store(
             Collider::cuboid(WINDOW_WIDTH / 2.0, wall_thickness),
             Sensor,
             CollisionGroups::new(GROUP_SENSOR_DEAD_ZONE, GROUP_BALL),
+            SensorWall,
         ))
         .id();
     commands.entity(s_entity).insert_children(0, &[s_collider]);
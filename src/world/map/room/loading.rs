@@ -1,17 +1,18 @@
 use bevy::{ecs::system::SystemId, prelude::*, state::commands};
 
 use crate::{
-    brick::{stats::BrickStats, Brick},
-    enemy::{
-        envy::SpawnEnvyEvent, gluttony::SpawnGluttonyEvent, greed::SpawnGreedEvent,
-        lust::SpawnLustEvent, pride::SpawnPrideEvent, sloth::SpawnSlothEvent,
-        wrath::SpawnWrathEvent,
+    brick::{
+        player::{PlayMode, Player},
+        stats::BrickStats,
+        Brick,
     },
+    difficulty::DifficultyScaling,
+    enemy::EnemySpawner,
     world::map::pipelines_readdy::{PipelinesReady, PipelinesReadyPlugin},
-    GameState,
+    GameState, WINDOW_HEIGHT, WINDOW_WIDTH,
 };
 
-use super::{create, select::SelectedRooms, ChooseState, Enemy, RoomType};
+use super::{create, select::SelectedRooms, ChooseState, RoomType};
 
 pub struct LoadingPlugin;
 
@@ -19,6 +20,9 @@ impl Plugin for LoadingPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(PipelinesReadyPlugin)
             .add_systems(Startup, load_loading_screen)
+            .init_resource::<CurrentRoom>()
+            .add_systems(OnEnter(GameState::Gaming), reset_current_room)
+            .add_systems(OnEnter(LoadingState::FadeIn), track_current_room)
             .add_systems(OnEnter(LoadingState::FadeOut), init_room_data)
             .add_systems(
                 OnExit(LoadingState::FadeOut),
@@ -44,6 +48,35 @@ pub enum LoadingState {
     FadeIn,  // 淡入状态（场景切换后）
 }
 
+// 砖块所在的房间，SelectedRooms 在淡入结束后就被清空了
+#[derive(Resource, Debug)]
+pub struct CurrentRoom {
+    pub room_type: RoomType,
+    pub has_encounter: bool,
+}
+
+impl Default for CurrentRoom {
+    fn default() -> Self {
+        Self {
+            room_type: RoomType::Start,
+            has_encounter: false,
+        }
+    }
+}
+
+fn reset_current_room(mut current_room: ResMut<CurrentRoom>) {
+    *current_room = CurrentRoom::default();
+}
+
+fn track_current_room(mut current_room: ResMut<CurrentRoom>, selected_rooms: Res<SelectedRooms>) {
+    if let Some(room) = selected_rooms.rooms.get(selected_rooms.index) {
+        *current_room = CurrentRoom {
+            room_type: room.room_type,
+            has_encounter: room.encounter.is_some(),
+        };
+    }
+}
+
 // 淡入淡出组件
 #[derive(Component)]
 struct FadeTransition {
@@ -152,61 +185,26 @@ fn load_next_room(mut commands: Commands, room_data: Res<RoomData>) {
     commands.run_system(room_data.load_room_enemys_id);
 }
 
+// 错开的敌人不能超出这个范围，离墙留出一点距离，也不会压到砖块上
+const ENEMY_AREA_MIN: Vec2 = Vec2::new(-WINDOW_WIDTH / 2.0 + 100.0, -120.0);
+const ENEMY_AREA_MAX: Vec2 = Vec2::new(WINDOW_WIDTH / 2.0 - 100.0, WINDOW_HEIGHT / 2.0 - 100.0);
+
+// 敌人数量按难度和深度增减，多出来的敌人按遭遇的顺序重复，并往右下方错开
 fn load_enemys(
     selected_rooms: Res<SelectedRooms>,
-    mut spawn_sloth_events_writer: EventWriter<SpawnSlothEvent>,
-    mut spawn_envy_events_writer: EventWriter<SpawnEnvyEvent>,
-    mut spawn_gluttony_events_writer: EventWriter<SpawnGluttonyEvent>,
-    mut spawn_greed_events_writer: EventWriter<SpawnGreedEvent>,
-    mut spawn_pride_events_writer: EventWriter<SpawnPrideEvent>,
-    mut spawn_wrath_events_writer: EventWriter<SpawnWrathEvent>,
-    mut spawn_lust_events_writer: EventWriter<SpawnLustEvent>,
+    mut enemy_spawner: EnemySpawner,
+    scaling: DifficultyScaling,
 ) {
     if let Some(select_room) = selected_rooms.rooms.get(selected_rooms.index) {
         if let Some(enconter) = &select_room.encounter {
-            for enemy_entity in enconter.enemys.iter() {
-                match enemy_entity.enemy_type {
-                    Enemy::Sloth => {
-                        spawn_sloth_events_writer.send(SpawnSlothEvent {
-                            pos: enemy_entity.position.into(),
-                        });
-                    }
-                    Enemy::BossA => {
-                        spawn_sloth_events_writer.send(SpawnSlothEvent {
-                            pos: enemy_entity.position.into(),
-                        });
-                    }
-                    Enemy::Envy => {
-                        spawn_envy_events_writer.send(SpawnEnvyEvent {
-                            pos: enemy_entity.position.into(),
-                        });
-                    }
-                    Enemy::Gluttony => {
-                        spawn_gluttony_events_writer.send(SpawnGluttonyEvent {
-                            pos: enemy_entity.position.into(),
-                        });
-                    }
-                    Enemy::Greed => {
-                        spawn_greed_events_writer.send(SpawnGreedEvent {
-                            pos: enemy_entity.position.into(),
-                        });
-                    }
-                    Enemy::Pride => {
-                        spawn_pride_events_writer.send(SpawnPrideEvent {
-                            pos: enemy_entity.position.into(),
-                        });
-                    }
-                    Enemy::Wrath => {
-                        spawn_wrath_events_writer.send(SpawnWrathEvent {
-                            pos: enemy_entity.position.into(),
-                        });
-                    }
-                    Enemy::Lust => {
-                        spawn_lust_events_writer.send(SpawnLustEvent {
-                            pos: enemy_entity.position.into(),
-                        });
-                    }
-                }
+            let base = enconter.enemys.len();
+            let count = scaling.enemy_count(base);
+            for (index, enemy_entity) in enconter.enemys.iter().cycle().take(count).enumerate() {
+                let round = (index / base) as f32;
+                let position: Vec2 = enemy_entity.position.into();
+                let position = (position + Vec2::new(70.0, -50.0) * round)
+                    .clamp(ENEMY_AREA_MIN, ENEMY_AREA_MAX);
+                enemy_spawner.spawn(enemy_entity.enemy_type, position);
             }
         }
     }
@@ -242,7 +240,8 @@ fn fade_control(
     loading_state: Res<State<LoadingState>>,
     mut next_loading_state: ResMut<NextState<LoadingState>>,
     mut next_choose_state: ResMut<NextState<ChooseState>>,
-    mut brick_transform: Single<&mut Transform, With<Brick>>,
+    mut q_brick: Query<(&mut Transform, &Player), With<Brick>>,
+    play_mode: Res<PlayMode>,
     mut brick_stats: ResMut<BrickStats>,
 ) {
     for (mut transition, mut color) in &mut query {
@@ -262,7 +261,9 @@ fn fade_control(
                     // 淡出完成后开始加载场景
                     println!("finish fade out");
                     next_loading_state.set(LoadingState::FadeIn);
-                    brick_transform.translation = Vec3::new(0.0, -250.0, 1.0);
+                    for (mut brick_transform, player) in q_brick.iter_mut() {
+                        brick_transform.translation = player.spawn_position(*play_mode);
+                    }
                     transition.timer.reset();
                 }
                 LoadingState::FadeIn => {